@@ -1,29 +1,47 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 mod wireguard;
 
+use std::collections::HashMap;
+use std::sync::Mutex;
 use wireguard::*;
 
+/// Lazily-initialized `TunnelManager`, so app startup doesn't fail on a
+/// machine without WireGuard tooling installed - only commands that
+/// actually need it do.
+struct ManagedTunnels(Mutex<Option<wireguard::manager::TunnelManager>>);
+
+fn with_tunnel_manager<R>(
+    state: &tauri::State<ManagedTunnels>,
+    f: impl FnOnce(&mut wireguard::manager::TunnelManager) -> Result<R, wireguard::platform::WireGuardError>,
+) -> Result<R, String> {
+    let mut guard = state.0.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(wireguard::manager::TunnelManager::new().map_err(|e| e.to_string())?);
+    }
+    f(guard.as_mut().unwrap()).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-/// Get a mock WireGuard config
+/// Save (or overwrite) a named config to the on-disk config store
 #[tauri::command]
-fn get_mock_config() -> WireGuardConfig {
-    wireguard::get_config()
+fn save_config(config: WireGuardConfig) -> Result<(), String> {
+    wireguard::store::save_config(&config).map_err(|e| e.to_string())
 }
 
-/// Fetch list of available configs from server (mocked)
+/// Delete a named config from the on-disk config store
 #[tauri::command]
-async fn fetch_config_list() -> Result<ServerConfigResponse, String> {
-    Ok(wireguard::fetch_config_list_from_server().await)
+fn delete_config(name: String) -> Result<bool, String> {
+    wireguard::store::delete_config(&name).map_err(|e| e.to_string())
 }
 
-/// Get a specific config by ID from server (mocked)
+/// List every config persisted in the on-disk config store
 #[tauri::command]
-async fn get_config_by_id(id: String) -> Result<Option<WireGuardConfig>, String> {
-    Ok(wireguard::get_config_by_id(&id))
+fn list_configs() -> Result<Vec<WireGuardConfig>, String> {
+    wireguard::store::list_configs().map_err(|e| e.to_string())
 }
 
 /// Get current connection status
@@ -32,12 +50,71 @@ fn get_connection_status() -> ConnectionStatus {
     wireguard::get_connection_status()
 }
 
+/// Get per-peer transfer/handshake statistics for an interface
+#[tauri::command]
+fn get_peer_stats(interface: String) -> Result<Vec<wireguard::PeerStats>, String> {
+    let platform = wireguard::platform::create_platform().map_err(|e| e.to_string())?;
+    let status = platform.get_status(Some(&interface)).map_err(|e| e.to_string())?;
+    Ok(status.peers)
+}
+
+/// Get the derived health ("disconnected" / "handshaking" / "active" /
+/// "stale") of an interface, for a UI that polls on an interval
+#[tauri::command]
+fn get_tunnel_health(interface: String) -> Result<String, String> {
+    let platform = wireguard::platform::create_platform().map_err(|e| e.to_string())?;
+    let status = platform.get_status(Some(&interface)).map_err(|e| e.to_string())?;
+    Ok(status.health().to_string())
+}
+
+/// Install WireGuard tooling via the platform's package manager if it's
+/// missing, then report which backend (kernel module vs. userspace
+/// wireguard-go) is actually usable
+#[tauri::command]
+fn ensure_wireguard_installed() -> Result<wireguard::platform::BackendInfo, String> {
+    wireguard::platform::create_native_platform().ensure_installed().map_err(|e| e.to_string())
+}
+
 /// Apply a WireGuard config
 #[tauri::command]
 async fn apply_config(config: WireGuardConfig) -> Result<String, String> {
     wireguard::apply_config(config).await
 }
 
+/// Apply a WireGuard config via the real platform backend, blocking until a
+/// peer ping confirms the tunnel actually carries traffic (or returning an
+/// error once `timeout_ms` elapses without a successful probe)
+#[tauri::command]
+fn apply_config_and_wait(config: WireGuardConfig, peer_ip: String, timeout_ms: u64) -> Result<String, String> {
+    let platform = wireguard::platform::create_platform().map_err(|e| e.to_string())?;
+    let readiness = wireguard::platform::ReadinessConfig::new(
+        vec![wireguard::platform::ReadinessProbe::Ping { target: peer_ip, count: 1 }],
+        std::time::Duration::from_millis(timeout_ms),
+    );
+    wireguard::platform::apply_config_with_readiness(platform.as_ref(), &config, Some(&readiness))
+        .map_err(|e| e.to_string())
+}
+
+/// Start a background handshake watchdog for an already-connected
+/// interface, which polls on an interval and reapplies `config` whenever the
+/// newest peer handshake goes stale - a self-healing tunnel instead of a
+/// one-shot `apply_config`
+#[tauri::command]
+async fn watch_tunnel(interface: String, config: WireGuardConfig) -> Result<(), String> {
+    let platform: std::sync::Arc<dyn wireguard::platform::WireGuardPlatform> =
+        std::sync::Arc::from(wireguard::platform::create_platform().map_err(|e| e.to_string())?);
+
+    wireguard::monitor::spawn_watchdog(
+        platform,
+        interface,
+        config,
+        wireguard::monitor::WatchdogConfig::default(),
+        |_health| {},
+    );
+
+    Ok(())
+}
+
 /// Disconnect from VPN
 #[tauri::command]
 async fn disconnect() -> Result<String, String> {
@@ -50,19 +127,116 @@ fn config_to_wg_quick_format(config: WireGuardConfig) -> String {
     wireguard::config_to_wg_quick_format(&config)
 }
 
+/// Import an existing wg-quick `.conf` file from disk
+#[tauri::command]
+fn import_config_file(path: String) -> Result<WireGuardConfig, String> {
+    WireGuardConfig::from_file(std::path::Path::new(&path)).map_err(|e| e.to_string())
+}
+
+/// Add a peer to a config, auto-allocating its address if none is given
+#[tauri::command]
+fn add_peer(mut config: WireGuardConfig, name: String, public_key: String, ip: Option<std::net::IpAddr>) -> Result<WireGuardConfig, String> {
+    config.add_peer(&name, public_key, ip).map_err(|e| e.to_string())?;
+    Ok(config)
+}
+
+/// Remove a peer from a config by name
+#[tauri::command]
+fn remove_peer(mut config: WireGuardConfig, name: String) -> WireGuardConfig {
+    config.remove_peer(&name);
+    config
+}
+
+/// Generate a new Curve25519 keypair for a peer or interface
+#[tauri::command]
+fn generate_keypair() -> (String, String) {
+    let pair = wireguard::keys::generate_keypair();
+    (pair.private_key, pair.public_key)
+}
+
+/// Derive the public key for a base64 private key
+#[tauri::command]
+fn derive_public_key(private_key: String) -> Result<String, String> {
+    wireguard::keys::derive_public(&private_key).map_err(|e| e.to_string())
+}
+
+/// Save (or update) a named, persistent tunnel profile
+#[tauri::command]
+fn save_profile(profile: wireguard::state::TunnelProfile) -> Result<(), String> {
+    let mut state = wireguard::state::TunnelState::load().map_err(|e| e.to_string())?;
+    state.upsert_profile(profile).map_err(|e| e.to_string())
+}
+
+/// List the persisted tunnel profiles
+#[tauri::command]
+fn list_profiles() -> Result<Vec<wireguard::state::TunnelProfile>, String> {
+    let state = wireguard::state::TunnelState::load().map_err(|e| e.to_string())?;
+    Ok(state.profiles)
+}
+
+/// Bring up a saved config as a named tunnel, so several can be connected at once
+#[tauri::command]
+fn connect_tunnel(state: tauri::State<ManagedTunnels>, name: String) -> Result<String, String> {
+    with_tunnel_manager(&state, |manager| manager.connect(&name))
+}
+
+/// Bring up a saved, persistent tunnel profile, resolving its private key
+/// via `private_key_file` (generating and persisting one there if it's
+/// missing) instead of requiring the key inline in its config file
+#[tauri::command]
+fn connect_profile(state: tauri::State<ManagedTunnels>, name: String) -> Result<String, String> {
+    let tunnel_state = wireguard::state::TunnelState::load().map_err(|e| e.to_string())?;
+    let profile = tunnel_state
+        .profiles
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("No saved profile named '{}'", name))?;
+
+    with_tunnel_manager(&state, |manager| manager.connect_profile(&profile))
+}
+
+/// Tear down a named tunnel
+#[tauri::command]
+fn disconnect_tunnel(state: tauri::State<ManagedTunnels>, name: String) -> Result<(), String> {
+    with_tunnel_manager(&state, |manager| manager.disconnect(&name))
+}
+
+/// Status of every tunnel currently managed, keyed by name
+#[tauri::command]
+fn tunnel_status_all(state: tauri::State<ManagedTunnels>) -> Result<HashMap<String, ConnectionStatus>, String> {
+    with_tunnel_manager(&state, |manager| Ok(manager.status_all()))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(ManagedTunnels(Mutex::new(None)))
         .invoke_handler(tauri::generate_handler![
             greet,
-            get_mock_config,
-            fetch_config_list,
-            get_config_by_id,
+            save_config,
+            delete_config,
+            list_configs,
             get_connection_status,
+            get_peer_stats,
+            get_tunnel_health,
+            ensure_wireguard_installed,
             apply_config,
+            apply_config_and_wait,
+            watch_tunnel,
             disconnect,
-            config_to_wg_quick_format
+            config_to_wg_quick_format,
+            import_config_file,
+            add_peer,
+            remove_peer,
+            generate_keypair,
+            derive_public_key,
+            save_profile,
+            list_profiles,
+            connect_tunnel,
+            connect_profile,
+            disconnect_tunnel,
+            tunnel_status_all
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,34 +1,178 @@
 use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr};
+
+use crate::wireguard::platform::WireGuardError;
 
 // Platform-specific implementations
 pub mod platform;
 
-/// WireGuard configuration structure
+/// Key generation helpers
+pub mod keys;
+
+/// Persisted tunnel/profile state
+pub mod state;
+
+/// On-disk named-config store
+pub mod store;
+
+/// Multi-tunnel connection manager
+pub mod manager;
+
+/// Background handshake watchdog / auto-reconnect
+pub mod monitor;
+
+/// A single WireGuard peer entry within a config's `[Peer]` blocks.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WireGuardConfig {
+pub struct Peer {
     pub name: String,
-    pub private_key: String,
     pub public_key: String,
-    pub endpoint: String,
+    /// Optional preshared key layered on top of the Curve25519 exchange for
+    /// post-quantum symmetric-key hardening.
+    #[serde(default)]
+    pub preshared_key: Option<String>,
+    pub endpoint: Option<String>,
     pub allowed_ips: String,
-    pub dns: Option<String>,
-    pub address: String,
     pub persistent_keepalive: Option<u16>,
 }
 
-/// Server config item (simplified for list view)
+/// WireGuard configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ServerConfigItem {
-    pub id: String,
+pub struct WireGuardConfig {
     pub name: String,
-    pub location: String,
-    pub endpoint: String,
+    pub private_key: String,
+    /// Interface addresses (CIDR), emitted as one `Address =` line per entry.
+    pub addresses: Vec<String>,
+    /// DNS servers to push down to the tunnel, emitted as a single
+    /// comma-separated `DNS =` line.
+    #[serde(default)]
+    pub dns: Vec<String>,
+    /// Optional fwmark applied to outgoing WireGuard packets, used to steer
+    /// them into a policy-routing table instead of the default route.
+    #[serde(default)]
+    pub fwmark: Option<u32>,
+    /// Optional interface MTU override.
+    #[serde(default)]
+    pub mtu: Option<u32>,
+    /// Optional fixed UDP port to listen on, instead of a random one.
+    #[serde(default)]
+    pub listen_port: Option<u16>,
+    pub peers: Vec<Peer>,
 }
 
-/// Full server config response
+impl WireGuardConfig {
+    /// The peer used by the single-tunnel connect path (the first peer in
+    /// the list). Multi-tunnel/hub-and-spoke dispatch pushes all of
+    /// `peers`, but most of the platform layer still just needs "the" peer.
+    pub fn primary_peer(&self) -> Option<&Peer> {
+        self.peers.first()
+    }
+
+    /// Add a peer to this config, allocating the next free address on the
+    /// interface's subnet when `ip` isn't given. Rejects duplicate names.
+    pub fn add_peer(&mut self, name: &str, public_key: String, ip: Option<IpAddr>) -> Result<(), WireGuardError> {
+        if self.peers.iter().any(|p| p.name == name) {
+            return Err(WireGuardError::ConfigInvalid(format!("Peer '{}' already exists", name)));
+        }
+
+        let ip = match ip {
+            Some(ip) => ip,
+            None => self.next_free_ip()?,
+        };
+
+        self.peers.push(Peer {
+            name: name.to_string(),
+            public_key,
+            preshared_key: None,
+            endpoint: None,
+            allowed_ips: format!("{}/32", ip),
+            persistent_keepalive: Some(25),
+        });
+
+        Ok(())
+    }
+
+    /// Remove a peer by name. Returns `true` if a peer was removed.
+    pub fn remove_peer(&mut self, name: &str) -> bool {
+        let before = self.peers.len();
+        self.peers.retain(|p| p.name != name);
+        self.peers.len() != before
+    }
+
+    /// Scan the first interface address's CIDR and return the lowest host
+    /// address not already claimed by an existing peer's `allowed_ips` (or
+    /// by the interface address itself).
+    pub fn next_free_ip(&self) -> Result<IpAddr, WireGuardError> {
+        let primary = self.addresses.first()
+            .ok_or_else(|| WireGuardError::ConfigInvalid("Config has no interface address".to_string()))?;
+
+        let (network, prefix_len) = parse_ipv4_cidr(primary)
+            .ok_or_else(|| WireGuardError::ConfigInvalid(format!("Invalid interface address: {}", primary)))?;
+
+        let mut used: Vec<Ipv4Addr> = vec![network_host(primary).unwrap_or(network)];
+        for peer in &self.peers {
+            for cidr in peer.allowed_ips.split(',').map(str::trim) {
+                if let Some(addr) = network_host(cidr) {
+                    used.push(addr);
+                }
+            }
+        }
+
+        let host_bits = 32 - prefix_len as u32;
+        let network_u32 = u32::from(network);
+        let broadcast = network_u32 | (1u32.checked_shl(host_bits).unwrap_or(0).wrapping_sub(1));
+
+        for host in (network_u32 + 1)..broadcast {
+            let candidate = Ipv4Addr::from(host);
+            if !used.contains(&candidate) {
+                return Ok(IpAddr::V4(candidate));
+            }
+        }
+
+        Err(WireGuardError::ConfigInvalid("No free IP addresses remaining in subnet".to_string()))
+    }
+
+    /// Import an existing wg-quick `.conf` file, naming the resulting config
+    /// after the file's stem (e.g. `wg0.conf` -> `"wg0"`).
+    pub fn from_file(path: &std::path::Path) -> Result<Self, WireGuardError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to read {}: {}", path.display(), e)))?;
+
+        let mut config = config_from_wg_quick_format(&contents)?;
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            config.name = stem.to_string();
+        }
+
+        Ok(config)
+    }
+}
+
+/// Parse `"10.0.0.2/24"` into its network address and prefix length.
+fn parse_ipv4_cidr(cidr: &str) -> Option<(Ipv4Addr, u8)> {
+    let (addr, prefix) = cidr.split_once('/')?;
+    let addr: Ipv4Addr = addr.parse().ok()?;
+    let prefix: u8 = prefix.parse().ok()?;
+    if prefix > 32 {
+        return None;
+    }
+
+    let mask = if prefix == 0 { 0 } else { u32::MAX << (32 - prefix) };
+    Some((Ipv4Addr::from(u32::from(addr) & mask), prefix))
+}
+
+/// Extract the host address from a `"10.0.0.2/32"`-style CIDR string.
+fn network_host(cidr: &str) -> Option<Ipv4Addr> {
+    cidr.split('/').next()?.parse().ok()
+}
+
+/// Per-peer statistics parsed from `wg show <iface> dump`
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ServerConfigResponse {
-    pub configs: Vec<ServerConfigItem>,
+pub struct PeerStats {
+    pub public_key: String,
+    pub endpoint: Option<String>,
+    pub allowed_ips: String,
+    pub last_handshake: Option<std::time::SystemTime>,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
 }
 
 /// Connection status
@@ -37,116 +181,94 @@ pub struct ConnectionStatus {
     pub connected: bool,
     pub current_config: Option<String>, // config name or ID
     pub interface: Option<String>,
+    #[serde(default)]
+    pub peers: Vec<PeerStats>,
 }
 
-/// Mock function to get a fake WireGuard config
-/// This will be replaced with actual WireGuard library calls later
-pub fn get_config() -> WireGuardConfig {
-    WireGuardConfig {
-        name: "Default Config".to_string(),
-        private_key: "cF7B1i7pHVXo0jRGyTqNy5GZgQWQ6Y5vN8jH9kL2mP3q=".to_string(),
-        public_key: "xTk2K9vN8jH9kL2mP3qRGyTqNy5GZgQWQ6Y5vN8jH=".to_string(),
-        endpoint: "vpn.example.com:51820".to_string(),
-        allowed_ips: "0.0.0.0/0".to_string(),
-        dns: Some("1.1.1.1, 8.8.8.8".to_string()),
-        address: "10.0.0.2/24".to_string(),
-        persistent_keepalive: Some(25),
-    }
+/// Coarse tunnel health, derived from peer handshake freshness rather than
+/// stored, so it stays in sync with whatever produced `peers` (netlink dump,
+/// UAPI `get=1`, or `wg show ... dump`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TunnelHealth {
+    /// Not connected, or connected with no peers configured.
+    Disconnected,
+    /// Connected but at least one peer hasn't completed its first handshake.
+    Handshaking,
+    /// Every peer's most recent handshake is within the staleness threshold.
+    Active,
+    /// Connected and handshaked at least once, but the newest handshake is
+    /// older than the staleness threshold (default 3x a 25s keepalive).
+    Stale,
 }
 
-/// Mock function to get a config by ID from server
-/// This simulates fetching a specific config from the server
-pub fn get_config_by_id(id: &str) -> Option<WireGuardConfig> {
-    // Mock different configs based on ID
-    match id {
-        "us-east-1" => Some(WireGuardConfig {
-            name: "US East Server".to_string(),
-            private_key: "eF8C2j8qIYWYp1kSHzUrO6HahRXR7Z6wO9kI0lM3nQ4r=".to_string(),
-            public_key: "yUl3L0wOxO9kI0lM3nQ4rSHzUrO6HahRXR7Z6wO9kI=".to_string(),
-            endpoint: "us-east.vpn.example.com:51820".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: Some("1.1.1.1".to_string()),
-            address: "10.0.0.3/24".to_string(),
-            persistent_keepalive: Some(25),
-        }),
-        "us-west-1" => Some(WireGuardConfig {
-            name: "US West Server".to_string(),
-            private_key: "fG9D3k9rJZXZq2lTIaVsP7IbiSY8a7xP0lJ1mN4oR5s=".to_string(),
-            public_key: "zVm4M1xPyP0lJ1mN4oR5sTIaVsP7IbiSY8a7xP0lJ=".to_string(),
-            endpoint: "us-west.vpn.example.com:51820".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: Some("8.8.8.8".to_string()),
-            address: "10.0.0.4/24".to_string(),
-            persistent_keepalive: Some(25),
-        }),
-        "eu-central-1" => Some(WireGuardConfig {
-            name: "EU Central Server".to_string(),
-            private_key: "gH0E4l0sKaYar3mUJbWtQ8JcjTZ9b8yQ1mK2nO5pS6t=".to_string(),
-            public_key: "aWn5N2yQzQ1mK2nO5pS6tUJbWtQ8JcjTZ9b8yQ1mK=".to_string(),
-            endpoint: "eu-central.vpn.example.com:51820".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: Some("1.1.1.1, 1.0.0.1".to_string()),
-            address: "10.0.0.5/24".to_string(),
-            persistent_keepalive: Some(25),
-        }),
-        "asia-pacific-1" => Some(WireGuardConfig {
-            name: "Asia Pacific Server".to_string(),
-            private_key: "hI1F5m1tLbZbs4nVKcXuR9KdkUa0c9zR2nL3oP6qT7u=".to_string(),
-            public_key: "bXo6O3zR0R2nL3oP6qT7uVKcXuR9KdkUa0c9zR2nL=".to_string(),
-            endpoint: "asia-pac.vpn.example.com:51820".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: Some("8.8.8.8, 8.8.4.4".to_string()),
-            address: "10.0.0.6/24".to_string(),
-            persistent_keepalive: Some(25),
-        }),
-        _ => None,
+impl std::fmt::Display for TunnelHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TunnelHealth::Disconnected => "disconnected",
+            TunnelHealth::Handshaking => "handshaking",
+            TunnelHealth::Active => "active",
+            TunnelHealth::Stale => "stale",
+        };
+        write!(f, "{}", s)
     }
 }
 
-/// Mock function to fetch list of available configs from server
-/// This simulates an API call that returns a list of available VPN servers
-pub async fn fetch_config_list_from_server() -> ServerConfigResponse {
-    // Simulate network delay
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+impl ConnectionStatus {
+    /// Derive [`TunnelHealth`] using the default staleness threshold of 3x
+    /// wg-quick's usual 25s persistent keepalive.
+    pub fn health(&self) -> TunnelHealth {
+        self.health_with_threshold(std::time::Duration::from_secs(3 * 25))
+    }
 
-    ServerConfigResponse {
-        configs: vec![
-            ServerConfigItem {
-                id: "us-east-1".to_string(),
-                name: "US East Server".to_string(),
-                location: "New York, USA".to_string(),
-                endpoint: "us-east.vpn.example.com:51820".to_string(),
-            },
-            ServerConfigItem {
-                id: "us-west-1".to_string(),
-                name: "US West Server".to_string(),
-                location: "San Francisco, USA".to_string(),
-                endpoint: "us-west.vpn.example.com:51820".to_string(),
-            },
-            ServerConfigItem {
-                id: "eu-central-1".to_string(),
-                name: "EU Central Server".to_string(),
-                location: "Frankfurt, Germany".to_string(),
-                endpoint: "eu-central.vpn.example.com:51820".to_string(),
-            },
-            ServerConfigItem {
-                id: "asia-pacific-1".to_string(),
-                name: "Asia Pacific Server".to_string(),
-                location: "Tokyo, Japan".to_string(),
-                endpoint: "asia-pac.vpn.example.com:51820".to_string(),
-            },
-        ],
+    /// Derive [`TunnelHealth`], treating a peer's handshake as stale once
+    /// it's older than `threshold`.
+    pub fn health_with_threshold(&self, threshold: std::time::Duration) -> TunnelHealth {
+        if !self.connected || self.peers.is_empty() {
+            return TunnelHealth::Disconnected;
+        }
+
+        let mut newest_age: Option<std::time::Duration> = None;
+        for peer in &self.peers {
+            let Some(handshake) = peer.last_handshake else {
+                return TunnelHealth::Handshaking;
+            };
+            let age = handshake.elapsed().unwrap_or_default();
+            newest_age = Some(newest_age.map_or(age, |newest| newest.min(age)));
+        }
+
+        match newest_age {
+            Some(age) if age > threshold => TunnelHealth::Stale,
+            _ => TunnelHealth::Active,
+        }
     }
 }
 
-/// Mock function to get current connection status
-/// This will be replaced with actual WireGuard status checks later
+/// Report the current connection status by probing every interface the
+/// active platform backend knows about and returning the first one that's
+/// actually connected (or a disconnected status if none are, or no
+/// backend is available on this platform).
 pub fn get_connection_status() -> ConnectionStatus {
-    ConnectionStatus {
+    let disconnected = || ConnectionStatus {
         connected: false,
         current_config: None,
         interface: None,
+        peers: vec![],
+    };
+
+    let Ok(platform) = platform::create_platform() else {
+        return disconnected();
+    };
+
+    for interface in platform.list_interfaces().unwrap_or_default() {
+        if let Ok(status) = platform.get_status(Some(&interface)) {
+            if status.connected {
+                return status;
+            }
+        }
     }
+
+    disconnected()
 }
 
 /// Mock function to apply a WireGuard config
@@ -184,72 +306,230 @@ pub fn config_to_wg_quick_format(config: &WireGuardConfig) -> String {
 
     wg_config.push_str("[Interface]\n");
     wg_config.push_str(&format!("PrivateKey = {}\n", config.private_key));
-    wg_config.push_str(&format!("Address = {}\n", config.address));
 
-    if let Some(dns) = &config.dns {
-        wg_config.push_str(&format!("DNS = {}\n", dns));
+    for address in &config.addresses {
+        wg_config.push_str(&format!("Address = {}\n", address));
     }
 
-    wg_config.push_str("\n[Peer]\n");
-    wg_config.push_str(&format!("PublicKey = {}\n", config.public_key));
-    wg_config.push_str(&format!("Endpoint = {}\n", config.endpoint));
-    wg_config.push_str(&format!("AllowedIPs = {}\n", config.allowed_ips));
+    if !config.dns.is_empty() {
+        wg_config.push_str(&format!("DNS = {}\n", config.dns.join(", ")));
+    }
 
-    if let Some(keepalive) = config.persistent_keepalive {
-        wg_config.push_str(&format!("PersistentKeepalive = {}\n", keepalive));
+    if let Some(fwmark) = config.fwmark {
+        wg_config.push_str(&format!("FwMark = {}\n", fwmark));
+    }
+
+    if let Some(mtu) = config.mtu {
+        wg_config.push_str(&format!("MTU = {}\n", mtu));
+    }
+
+    if let Some(listen_port) = config.listen_port {
+        wg_config.push_str(&format!("ListenPort = {}\n", listen_port));
+    }
+
+    for peer in &config.peers {
+        wg_config.push_str("\n[Peer]\n");
+        wg_config.push_str(&format!("PublicKey = {}\n", peer.public_key));
+
+        if let Some(preshared_key) = &peer.preshared_key {
+            wg_config.push_str(&format!("PresharedKey = {}\n", preshared_key));
+        }
+
+        if let Some(endpoint) = &peer.endpoint {
+            wg_config.push_str(&format!("Endpoint = {}\n", endpoint));
+        }
+
+        wg_config.push_str(&format!("AllowedIPs = {}\n", peer.allowed_ips));
+
+        if let Some(keepalive) = peer.persistent_keepalive {
+            wg_config.push_str(&format!("PersistentKeepalive = {}\n", keepalive));
+        }
     }
 
     wg_config
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Parse a wg-quick `.conf` file (standard `[Interface]`/`[Peer]` INI
+/// sections) into a `WireGuardConfig`, the inverse of
+/// `config_to_wg_quick_format`. Tolerates blank lines, `#`/`;` comments, and
+/// repeated keys (multiple `Address`/`DNS`/`AllowedIPs` lines all merge into
+/// the same field). The imported config is named `"Imported Config"`;
+/// `WireGuardConfig::from_file` overrides this with the file's stem.
+///
+/// Rejects the input if the `[Interface]` section has no `PrivateKey`, or if
+/// any `[Peer]` section has no `PublicKey` or `Endpoint` - an importable
+/// client tunnel needs both to actually connect.
+pub fn config_from_wg_quick_format(input: &str) -> Result<WireGuardConfig, WireGuardError> {
+    #[derive(PartialEq)]
+    enum Section {
+        None,
+        Interface,
+        Peer,
+    }
 
-    #[test]
-    fn test_get_config() {
-        let config = get_config();
-        assert_eq!(config.name, "Default Config");
-        assert!(!config.private_key.is_empty());
-        assert!(!config.public_key.is_empty());
-        assert!(!config.endpoint.is_empty());
-        assert!(!config.address.is_empty());
+    let mut section = Section::None;
+    let mut private_key: Option<String> = None;
+    let mut addresses = Vec::new();
+    let mut dns = Vec::new();
+    let mut fwmark = None;
+    let mut mtu = None;
+    let mut listen_port = None;
+    let mut peers: Vec<Peer> = Vec::new();
+
+    let mut public_key: Option<String> = None;
+    let mut preshared_key = None;
+    let mut endpoint = None;
+    let mut allowed_ips = Vec::new();
+    let mut persistent_keepalive = None;
+
+    fn finish_peer(
+        public_key: &mut Option<String>,
+        preshared_key: &mut Option<String>,
+        endpoint: &mut Option<String>,
+        allowed_ips: &mut Vec<String>,
+        persistent_keepalive: &mut Option<u16>,
+        peers: &mut Vec<Peer>,
+    ) -> Result<(), WireGuardError> {
+        let Some(public_key) = public_key.take() else { return Ok(()) };
+        let Some(endpoint) = endpoint.take() else {
+            return Err(WireGuardError::ConfigInvalid(format!(
+                "Peer '{}' is missing an Endpoint",
+                public_key
+            )));
+        };
+
+        peers.push(Peer {
+            name: format!("peer-{}", peers.len() + 1),
+            public_key,
+            preshared_key: preshared_key.take(),
+            endpoint: Some(endpoint),
+            allowed_ips: std::mem::take(allowed_ips).join(", "),
+            persistent_keepalive: persistent_keepalive.take(),
+        });
+
+        Ok(())
     }
 
-    #[test]
-    fn test_get_config_by_id() {
-        // Test existing configs
-        let us_east = get_config_by_id("us-east-1");
-        assert!(us_east.is_some());
-        assert_eq!(us_east.unwrap().name, "US East Server");
+    for (line_no, raw_line) in input.lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = strip_ini_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            if section == Section::Peer {
+                finish_peer(&mut public_key, &mut preshared_key, &mut endpoint, &mut allowed_ips, &mut persistent_keepalive, &mut peers)?;
+            }
+
+            section = match name.trim() {
+                "Interface" => Section::Interface,
+                "Peer" => Section::Peer,
+                other => return Err(WireGuardError::ConfigInvalid(format!("line {}: unknown section [{}]", line_no, other))),
+            };
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(WireGuardError::ConfigInvalid(format!("line {}: malformed line: {}", line_no, line)));
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        match section {
+            Section::Interface => match key {
+                "PrivateKey" => private_key = Some(value.to_string()),
+                "Address" => addresses.extend(value.split(',').map(|s| s.trim().to_string())),
+                "DNS" => dns.extend(value.split(',').map(|s| s.trim().to_string())),
+                "FwMark" => fwmark = value.parse().ok(),
+                "MTU" => mtu = value.parse().ok(),
+                "ListenPort" => listen_port = value.parse().ok(),
+                _ => {}
+            },
+            Section::Peer => match key {
+                "PublicKey" => public_key = Some(value.to_string()),
+                "PresharedKey" => preshared_key = Some(value.to_string()),
+                "Endpoint" => endpoint = Some(value.to_string()),
+                "AllowedIPs" => allowed_ips.extend(value.split(',').map(|s| s.trim().to_string())),
+                "PersistentKeepalive" => persistent_keepalive = value.parse().ok(),
+                _ => {}
+            },
+            Section::None => {
+                return Err(WireGuardError::ConfigInvalid(format!("line {}: key outside of a section: {}", line_no, key)));
+            }
+        }
+    }
+
+    finish_peer(&mut public_key, &mut preshared_key, &mut endpoint, &mut allowed_ips, &mut persistent_keepalive, &mut peers)?;
 
-        let us_west = get_config_by_id("us-west-1");
-        assert!(us_west.is_some());
-        assert_eq!(us_west.unwrap().name, "US West Server");
+    let private_key = private_key
+        .ok_or_else(|| WireGuardError::ConfigInvalid("Missing required [Interface] key: PrivateKey".to_string()))?;
 
-        let eu_central = get_config_by_id("eu-central-1");
-        assert!(eu_central.is_some());
-        assert_eq!(eu_central.unwrap().name, "EU Central Server");
+    if peers.is_empty() {
+        return Err(WireGuardError::ConfigInvalid("Config has no [Peer] sections".to_string()));
+    }
+
+    Ok(WireGuardConfig {
+        name: "Imported Config".to_string(),
+        private_key,
+        addresses,
+        dns,
+        fwmark,
+        mtu,
+        listen_port,
+        peers,
+    })
+}
 
-        let asia_pacific = get_config_by_id("asia-pacific-1");
-        assert!(asia_pacific.is_some());
-        assert_eq!(asia_pacific.unwrap().name, "Asia Pacific Server");
+/// Strip a wg-quick style `#`/`;` trailing comment from an INI line.
+fn strip_ini_comment(line: &str) -> &str {
+    line.find(['#', ';']).map(|i| &line[..i]).unwrap_or(line)
+}
+
+/// Alias for [`config_from_wg_quick_format`] under the name users importing
+/// a `wg0.conf` are more likely to reach for.
+pub fn parse_wg_quick_config(input: &str) -> Result<WireGuardConfig, WireGuardError> {
+    config_from_wg_quick_format(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Test non-existent config
-        let invalid = get_config_by_id("invalid-id");
-        assert!(invalid.is_none());
+    fn test_peer(name: &str, public_key: &str, endpoint: &str) -> Peer {
+        Peer {
+            name: name.to_string(),
+            public_key: public_key.to_string(),
+            preshared_key: None,
+            endpoint: Some(endpoint.to_string()),
+            allowed_ips: "0.0.0.0/0".to_string(),
+            persistent_keepalive: Some(25),
+        }
     }
 
-    #[tokio::test]
-    async fn test_fetch_config_list_from_server() {
-        let response = fetch_config_list_from_server().await;
-        assert_eq!(response.configs.len(), 4);
+    /// A config fixture for the tests below, replacing the old hardcoded
+    /// mock server list now that configs come from the on-disk store.
+    fn sample_config() -> WireGuardConfig {
+        WireGuardConfig {
+            name: "Default Config".to_string(),
+            private_key: "cF7B1i7pHVXo0jRGyTqNy5GZgQWQ6Y5vN8jH9kL2mP3q=".to_string(),
+            dns: vec!["1.1.1.1".to_string(), "8.8.8.8".to_string()],
+            addresses: vec!["10.0.0.2/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![test_peer("default-peer", "xTk2K9vN8jH9kL2mP3qRGyTqNy5GZgQWQ6Y5vN8jH=", "vpn.example.com:51820")],
+        }
+    }
 
-        assert_eq!(response.configs[0].id, "us-east-1");
-        assert_eq!(response.configs[0].name, "US East Server");
-        assert_eq!(response.configs[1].id, "us-west-1");
-        assert_eq!(response.configs[2].id, "eu-central-1");
-        assert_eq!(response.configs[3].id, "asia-pacific-1");
+    #[test]
+    fn test_sample_config() {
+        let config = sample_config();
+        assert_eq!(config.name, "Default Config");
+        assert!(!config.private_key.is_empty());
+        assert!(!config.addresses.is_empty());
+        assert!(!config.peers.is_empty());
+        assert!(!config.peers[0].public_key.is_empty());
+        assert!(config.peers[0].endpoint.is_some());
     }
 
     #[test]
@@ -260,17 +540,53 @@ mod tests {
         assert!(status.interface.is_none());
     }
 
+    fn peer_stats(last_handshake: Option<std::time::SystemTime>) -> PeerStats {
+        PeerStats {
+            public_key: "test-public-key".to_string(),
+            endpoint: Some("vpn.example.com:51820".to_string()),
+            allowed_ips: "0.0.0.0/0".to_string(),
+            last_handshake,
+            rx_bytes: 0,
+            tx_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn test_health_disconnected_when_not_connected_or_no_peers() {
+        let status = ConnectionStatus { connected: false, current_config: None, interface: None, peers: vec![peer_stats(Some(std::time::SystemTime::now()))] };
+        assert_eq!(status.health(), TunnelHealth::Disconnected);
+
+        let status = ConnectionStatus { connected: true, current_config: None, interface: None, peers: vec![] };
+        assert_eq!(status.health(), TunnelHealth::Disconnected);
+    }
+
+    #[test]
+    fn test_health_handshaking_until_first_handshake() {
+        let status = ConnectionStatus { connected: true, current_config: None, interface: None, peers: vec![peer_stats(None)] };
+        assert_eq!(status.health(), TunnelHealth::Handshaking);
+    }
+
+    #[test]
+    fn test_health_active_and_stale_by_threshold() {
+        let fresh = ConnectionStatus { connected: true, current_config: None, interface: None, peers: vec![peer_stats(Some(std::time::SystemTime::now()))] };
+        assert_eq!(fresh.health_with_threshold(std::time::Duration::from_secs(60)), TunnelHealth::Active);
+
+        let old_handshake = std::time::SystemTime::now() - std::time::Duration::from_secs(120);
+        let stale = ConnectionStatus { connected: true, current_config: None, interface: None, peers: vec![peer_stats(Some(old_handshake))] };
+        assert_eq!(stale.health_with_threshold(std::time::Duration::from_secs(60)), TunnelHealth::Stale);
+    }
+
     #[tokio::test]
     async fn test_apply_config() {
         let config = WireGuardConfig {
             name: "Test Config".to_string(),
             private_key: "test-private-key".to_string(),
-            public_key: "test-public-key".to_string(),
-            endpoint: "test.example.com:51820".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: Some("1.1.1.1".to_string()),
-            address: "10.0.0.1/24".to_string(),
-            persistent_keepalive: Some(25),
+            dns: vec!["1.1.1.1".to_string()],
+            addresses: vec!["10.0.0.1/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![test_peer("peer-1", "test-public-key", "test.example.com:51820")],
         };
 
         let result = apply_config(config).await;
@@ -290,12 +606,12 @@ mod tests {
         let config = WireGuardConfig {
             name: "Test Config".to_string(),
             private_key: "test-private-key".to_string(),
-            public_key: "test-public-key".to_string(),
-            endpoint: "test.example.com:51820".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: Some("1.1.1.1".to_string()),
-            address: "10.0.0.1/24".to_string(),
-            persistent_keepalive: Some(25),
+            dns: vec!["1.1.1.1".to_string()],
+            addresses: vec!["10.0.0.1/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![test_peer("peer-1", "test-public-key", "test.example.com:51820")],
         };
 
         let wg_format = config_to_wg_quick_format(&config);
@@ -311,17 +627,69 @@ mod tests {
         assert!(wg_format.contains("PersistentKeepalive = 25"));
     }
 
+    #[test]
+    fn test_config_to_wg_quick_format_includes_fwmark() {
+        let mut config = WireGuardConfig {
+            name: "Test Config".to_string(),
+            private_key: "test-private-key".to_string(),
+            dns: vec![],
+            addresses: vec!["10.0.0.1/24".to_string()],
+            fwmark: Some(51820),
+            mtu: None,
+            listen_port: None,
+            peers: vec![test_peer("peer-1", "test-public-key", "test.example.com:51820")],
+        };
+
+        assert!(config_to_wg_quick_format(&config).contains("FwMark = 51820"));
+
+        config.fwmark = None;
+        assert!(!config_to_wg_quick_format(&config).contains("FwMark"));
+    }
+
+    #[test]
+    fn test_config_to_wg_quick_format_multiple_addresses_mtu_and_preshared_key() {
+        let mut peer = test_peer("peer-1", "test-public-key", "test.example.com:51820");
+        peer.preshared_key = Some("preshared-key-value".to_string());
+
+        let config = WireGuardConfig {
+            name: "Test Config".to_string(),
+            private_key: "test-private-key".to_string(),
+            dns: vec!["1.1.1.1".to_string(), "8.8.8.8".to_string()],
+            addresses: vec!["10.0.0.1/24".to_string(), "fd00::1/64".to_string()],
+            fwmark: None,
+            mtu: Some(1420),
+            listen_port: None,
+            peers: vec![peer],
+        };
+
+        let wg_format = config_to_wg_quick_format(&config);
+
+        assert_eq!(wg_format.matches("Address =").count(), 2);
+        assert!(wg_format.contains("Address = 10.0.0.1/24"));
+        assert!(wg_format.contains("Address = fd00::1/64"));
+        assert!(wg_format.contains("DNS = 1.1.1.1, 8.8.8.8"));
+        assert!(wg_format.contains("MTU = 1420"));
+        assert!(wg_format.contains("PresharedKey = preshared-key-value"));
+    }
+
     #[test]
     fn test_config_to_wg_quick_format_without_optional_fields() {
         let config = WireGuardConfig {
             name: "Test Config".to_string(),
             private_key: "test-private-key".to_string(),
-            public_key: "test-public-key".to_string(),
-            endpoint: "test.example.com:51820".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: None,
-            address: "10.0.0.1/24".to_string(),
-            persistent_keepalive: None,
+            dns: vec![],
+            addresses: vec!["10.0.0.1/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![Peer {
+                name: "peer-1".to_string(),
+                public_key: "test-public-key".to_string(),
+                preshared_key: None,
+                endpoint: None,
+                allowed_ips: "0.0.0.0/0".to_string(),
+                persistent_keepalive: None,
+            }],
         };
 
         let wg_format = config_to_wg_quick_format(&config);
@@ -338,12 +706,12 @@ mod tests {
         let config = WireGuardConfig {
             name: "Test".to_string(),
             private_key: "key".to_string(),
-            public_key: "pub".to_string(),
-            endpoint: "endpoint".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: Some("1.1.1.1".to_string()),
-            address: "10.0.0.1/24".to_string(),
-            persistent_keepalive: Some(25),
+            dns: vec!["1.1.1.1".to_string()],
+            addresses: vec!["10.0.0.1/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![test_peer("peer-1", "pub", "endpoint")],
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -351,7 +719,121 @@ mod tests {
 
         assert_eq!(config.name, deserialized.name);
         assert_eq!(config.private_key, deserialized.private_key);
-        assert_eq!(config.public_key, deserialized.public_key);
+        assert_eq!(config.peers[0].public_key, deserialized.peers[0].public_key);
     }
-}
 
+    #[test]
+    fn test_add_peer_allocates_next_free_ip() {
+        let mut config = sample_config();
+        config.addresses = vec!["10.0.0.1/29".to_string()];
+        config.peers = vec![];
+
+        config.add_peer("laptop", "laptop-public-key".to_string(), None).unwrap();
+        assert_eq!(config.peers[0].allowed_ips, "10.0.0.2/32");
+
+        config.add_peer("phone", "phone-public-key".to_string(), None).unwrap();
+        assert_eq!(config.peers[1].allowed_ips, "10.0.0.3/32");
+    }
+
+    #[test]
+    fn test_add_peer_rejects_duplicate_names() {
+        let mut config = sample_config();
+        config.addresses = vec!["10.0.0.1/24".to_string()];
+        config.peers = vec![];
+
+        config.add_peer("laptop", "key-a".to_string(), None).unwrap();
+        let result = config.add_peer("laptop", "key-b".to_string(), None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_remove_peer() {
+        let mut config = sample_config();
+        assert!(config.remove_peer("default-peer"));
+        assert!(config.peers.is_empty());
+        assert!(!config.remove_peer("default-peer"));
+    }
+
+    #[test]
+    fn test_config_from_wg_quick_format_round_trips() {
+        let mut peer = test_peer("peer-1", "test-public-key", "test.example.com:51820");
+        peer.preshared_key = Some("preshared-key-value".to_string());
+
+        let config = WireGuardConfig {
+            name: "original-name".to_string(),
+            private_key: "test-private-key".to_string(),
+            dns: vec!["1.1.1.1".to_string(), "8.8.8.8".to_string()],
+            addresses: vec!["10.0.0.1/24".to_string()],
+            fwmark: Some(51820),
+            mtu: Some(1420),
+            listen_port: Some(51821),
+            peers: vec![peer],
+        };
+
+        let wg_format = config_to_wg_quick_format(&config);
+        let parsed = config_from_wg_quick_format(&wg_format).unwrap();
+
+        assert_eq!(parsed.private_key, config.private_key);
+        assert_eq!(parsed.addresses, config.addresses);
+        assert_eq!(parsed.dns, config.dns);
+        assert_eq!(parsed.fwmark, config.fwmark);
+        assert_eq!(parsed.mtu, config.mtu);
+        assert_eq!(parsed.listen_port, config.listen_port);
+        assert_eq!(parsed.peers[0].public_key, config.peers[0].public_key);
+        assert_eq!(parsed.peers[0].preshared_key, config.peers[0].preshared_key);
+        assert_eq!(parsed.peers[0].endpoint, config.peers[0].endpoint);
+        assert_eq!(parsed.peers[0].allowed_ips, config.peers[0].allowed_ips);
+    }
+
+    #[test]
+    fn test_config_from_wg_quick_format_tolerates_comments_and_repeated_keys() {
+        let input = "
+            # A hand-edited config
+            [Interface]
+            PrivateKey = test-private-key ; inline comment
+            Address = 10.0.0.2/24
+            Address = fd00::2/64
+            DNS = 1.1.1.1
+
+            [Peer]
+            PublicKey = test-public-key
+            AllowedIPs = 10.0.0.0/24
+            AllowedIPs = fd00::/64
+            Endpoint = vpn.example.com:51820
+        ";
+
+        let config = config_from_wg_quick_format(input).unwrap();
+        assert_eq!(config.addresses, vec!["10.0.0.2/24".to_string(), "fd00::2/64".to_string()]);
+        assert_eq!(config.peers[0].allowed_ips, "10.0.0.0/24, fd00::/64");
+    }
+
+    #[test]
+    fn test_config_from_wg_quick_format_rejects_missing_private_key() {
+        let input = "[Peer]\nPublicKey = test-public-key\nEndpoint = vpn.example.com:51820\n";
+        let result = config_from_wg_quick_format(input);
+        assert!(matches!(result, Err(WireGuardError::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_config_from_wg_quick_format_rejects_peer_missing_endpoint() {
+        let input = "[Interface]\nPrivateKey = test-private-key\n\n[Peer]\nPublicKey = test-public-key\n";
+        let result = config_from_wg_quick_format(input);
+        assert!(matches!(result, Err(WireGuardError::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_config_from_wg_quick_format_malformed_line_reports_line_number() {
+        let input = "[Interface]\nPrivateKey = test-private-key\nNotAKeyValuePair\n";
+        let err = config_from_wg_quick_format(input).unwrap_err();
+        let WireGuardError::ConfigInvalid(msg) = err else { panic!("expected ConfigInvalid") };
+        assert!(msg.contains("line 3"), "error should mention the offending line: {}", msg);
+    }
+
+    #[test]
+    fn test_parse_wg_quick_config_is_an_alias_for_config_from_wg_quick_format() {
+        let input = "[Interface]\nPrivateKey = test-private-key\nAddress = 10.0.0.2/24\n\n[Peer]\nPublicKey = test-public-key\nEndpoint = vpn.example.com:51820\nAllowedIPs = 0.0.0.0/0\n";
+        let parsed = parse_wg_quick_config(input).unwrap();
+        assert_eq!(parsed.private_key, "test-private-key");
+        assert_eq!(parsed.peers[0].public_key, "test-public-key");
+    }
+}
@@ -0,0 +1,130 @@
+/// Curve25519 keypair generation for WireGuard.
+///
+/// WireGuard keys are X25519 scalars/points, base64-encoded the same way
+/// `wg genkey`/`wg pubkey` produce them.
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use super::platform::WireGuardError;
+
+/// A generated (or derived) WireGuard keypair, base64-encoded.
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    pub private_key: String,
+    pub public_key: String,
+}
+
+/// Generate a new random keypair from the OS CSPRNG.
+pub fn generate_keypair() -> KeyPair {
+    let secret = StaticSecret::random_from_rng(rand::rngs::OsRng);
+    let public = PublicKey::from(&secret);
+
+    KeyPair {
+        private_key: STANDARD.encode(secret.to_bytes()),
+        public_key: STANDARD.encode(public.to_bytes()),
+    }
+}
+
+/// Derive the base64 public key for a base64 private key, the same way
+/// `wg pubkey` would from a piped `wg genkey` output.
+pub fn derive_public(private_key: &str) -> Result<String, WireGuardError> {
+    let bytes = STANDARD.decode(private_key)
+        .map_err(|e| WireGuardError::ConfigInvalid(format!("Invalid private key: {}", e)))?;
+    let bytes: [u8; 32] = bytes.try_into()
+        .map_err(|_| WireGuardError::ConfigInvalid("Private key must be 32 bytes".to_string()))?;
+
+    let secret = StaticSecret::from(bytes);
+    let public = PublicKey::from(&secret);
+    Ok(STANDARD.encode(public.to_bytes()))
+}
+
+/// Where an interface's private key actually lives, following the NixOS
+/// `privateKeyFile` pattern of keeping the key out of whatever serializes
+/// the surrounding config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PrivateKeySource {
+    /// The key itself, embedded directly (the status quo for `WireGuardConfig`).
+    Inline(String),
+    /// A path to a file holding the base64 key, generated on first use if absent.
+    File(PathBuf),
+}
+
+impl PrivateKeySource {
+    /// Resolve to the actual base64 private key. For a `File` source, reads
+    /// the key if the file exists, or generates one, persists it, and
+    /// returns it if not - so callers never have to generate keys themselves.
+    pub fn resolve(&self) -> Result<String, WireGuardError> {
+        match self {
+            PrivateKeySource::Inline(key) => Ok(key.clone()),
+            PrivateKeySource::File(path) => {
+                if let Ok(contents) = std::fs::read_to_string(path) {
+                    return Ok(contents.trim().to_string());
+                }
+
+                let pair = generate_keypair();
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to create {}: {}", parent.display(), e)))?;
+                }
+                std::fs::write(path, &pair.private_key)
+                    .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to write {}: {}", path.display(), e)))?;
+
+                Ok(pair.private_key)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_keypair_produces_distinct_keys() {
+        let a = generate_keypair();
+        let b = generate_keypair();
+
+        assert_ne!(a.private_key, b.private_key);
+        assert_ne!(a.public_key, b.public_key);
+        assert_ne!(a.private_key, a.public_key);
+    }
+
+    #[test]
+    fn test_generate_keypair_keys_are_base64() {
+        let pair = generate_keypair();
+        assert!(STANDARD.decode(&pair.private_key).is_ok());
+        assert!(STANDARD.decode(&pair.public_key).is_ok());
+    }
+
+    #[test]
+    fn test_derive_public_matches_generated_pair() {
+        let pair = generate_keypair();
+        assert_eq!(derive_public(&pair.private_key).unwrap(), pair.public_key);
+    }
+
+    #[test]
+    fn test_derive_public_rejects_invalid_key() {
+        assert!(derive_public("not-valid-base64!!").is_err());
+    }
+
+    #[test]
+    fn test_private_key_source_inline_resolves_to_itself() {
+        let source = PrivateKeySource::Inline("some-key".to_string());
+        assert_eq!(source.resolve().unwrap(), "some-key");
+    }
+
+    #[test]
+    fn test_private_key_source_file_generates_and_persists_then_reuses() {
+        let path = std::env::temp_dir().join("vipn-test-private-key-source.key");
+        let _ = std::fs::remove_file(&path);
+
+        let source = PrivateKeySource::File(path.clone());
+        let generated = source.resolve().unwrap();
+        let reused = source.resolve().unwrap();
+
+        assert_eq!(generated, reused);
+        let _ = std::fs::remove_file(&path);
+    }
+}
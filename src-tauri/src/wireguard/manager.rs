@@ -0,0 +1,241 @@
+/// Manages several named tunnels concurrently, echoing the crosh
+/// `list`/`show`/`new`/`del`/`set` subcommand model instead of the
+/// single-global-connection assumption `apply_config`/`disconnect`/
+/// `get_connection_status` still make. Tunnels are looked up by name in the
+/// on-disk config store and tracked by the interface name the platform
+/// backend assigned them, so several can be up at once.
+use std::collections::HashMap;
+
+use super::platform::{create_platform, WireGuardError, WireGuardPlatform};
+use super::state::{TunnelProfile, TunnelState};
+use super::{store, ConnectionStatus, WireGuardConfig};
+
+pub struct TunnelManager {
+    platform: Box<dyn WireGuardPlatform>,
+    /// Interface name for each named tunnel currently managed.
+    interfaces: HashMap<String, String>,
+}
+
+impl TunnelManager {
+    /// Build a manager backed by the real platform, rehydrating tracked
+    /// interfaces from the already-persisted `TunnelState::active` - since
+    /// `ManagedTunnels` constructs exactly one `TunnelManager` lazily per app
+    /// process, skipping this would make `status`/`status_all`/`disconnect`
+    /// act as if nothing were connected after every app restart, even though
+    /// `connect`/`connect_profile`'s tunnels are still actually running.
+    pub fn new() -> Result<Self, WireGuardError> {
+        let platform = create_platform()?;
+        let interfaces = TunnelState::load()?
+            .active
+            .into_values()
+            .map(|active| (active.profile_name, active.interface))
+            .collect();
+
+        Ok(Self { platform, interfaces })
+    }
+
+    /// Build a manager around an explicit platform backend, e.g. a test double.
+    pub fn with_platform(platform: Box<dyn WireGuardPlatform>) -> Self {
+        Self { platform, interfaces: HashMap::new() }
+    }
+
+    /// Bring up the saved config named `name`, recording it both in this
+    /// manager and in the persisted `TunnelState` so it survives a restart.
+    pub fn connect(&mut self, name: &str) -> Result<String, WireGuardError> {
+        let config = store::get_config(name)?
+            .ok_or_else(|| WireGuardError::ConfigInvalid(format!("No saved config named '{}'", name)))?;
+
+        let interface = self.platform.apply_config(&config)?;
+        self.interfaces.insert(name.to_string(), interface.clone());
+
+        let mut state = TunnelState::load()?;
+        state.record_active(name, &interface, store::config_path(name)?)?;
+
+        Ok(interface)
+    }
+
+    /// Bring up a persistent `TunnelProfile`, resolving its private key via
+    /// `private_key_file` (generating and persisting one there if it's
+    /// missing) instead of requiring the key to already be inline in
+    /// `profile.config_path`'s `.conf` file.
+    pub fn connect_profile(&mut self, profile: &TunnelProfile) -> Result<String, WireGuardError> {
+        let mut config = WireGuardConfig::from_file(&profile.config_path)?;
+
+        if let Some(private_key) = profile.resolve_private_key()? {
+            config.private_key = private_key;
+        }
+
+        let interface = self.platform.apply_config(&config)?;
+        self.interfaces.insert(profile.name.clone(), interface.clone());
+
+        let mut state = TunnelState::load()?;
+        state.record_active(&profile.name, &interface, profile.config_path.clone())?;
+
+        Ok(interface)
+    }
+
+    /// Tear down the named tunnel. Errors with `InterfaceNotFound` if this
+    /// manager isn't tracking a tunnel by that name.
+    pub fn disconnect(&mut self, name: &str) -> Result<(), WireGuardError> {
+        let interface = self.interfaces.remove(name)
+            .ok_or_else(|| WireGuardError::InterfaceNotFound(name.to_string()))?;
+
+        self.platform.disconnect(&interface)?;
+
+        let mut state = TunnelState::load()?;
+        state.clear_active(&interface)?;
+
+        Ok(())
+    }
+
+    /// Status of one named tunnel this manager is tracking.
+    pub fn status(&self, name: &str) -> Result<ConnectionStatus, WireGuardError> {
+        let interface = self.interfaces.get(name)
+            .ok_or_else(|| WireGuardError::InterfaceNotFound(name.to_string()))?;
+
+        self.platform.get_status(Some(interface))
+    }
+
+    /// Status of every tunnel this manager is currently tracking, keyed by
+    /// name. A tunnel whose status can't be fetched is omitted rather than
+    /// failing the whole call.
+    pub fn status_all(&self) -> HashMap<String, ConnectionStatus> {
+        self.interfaces.keys()
+            .filter_map(|name| self.status(name).ok().map(|status| (name.clone(), status)))
+            .collect()
+    }
+
+    /// Names of the tunnels currently tracked by this manager.
+    pub fn names(&self) -> Vec<String> {
+        self.interfaces.keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wireguard::{Peer, WireGuardConfig};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A fake platform that hands out a fresh interface name per
+    /// `apply_config` call and reports every known interface as connected,
+    /// so tests don't depend on a real kernel/userspace backend.
+    struct FakePlatform {
+        next_id: AtomicUsize,
+    }
+
+    impl FakePlatform {
+        fn new() -> Self {
+            Self { next_id: AtomicUsize::new(0) }
+        }
+    }
+
+    impl WireGuardPlatform for FakePlatform {
+        fn apply_config(&self, _config: &WireGuardConfig) -> Result<String, WireGuardError> {
+            let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("wg-test-{}", id))
+        }
+        fn disconnect(&self, _interface: &str) -> Result<(), WireGuardError> {
+            Ok(())
+        }
+        fn get_status(&self, interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
+            Ok(ConnectionStatus {
+                connected: true,
+                current_config: None,
+                interface: interface.map(String::from),
+                peers: vec![],
+            })
+        }
+        fn list_interfaces(&self) -> Result<Vec<String>, WireGuardError> {
+            Ok(vec![])
+        }
+        fn is_wireguard_installed(&self) -> bool {
+            true
+        }
+        fn wg_quick_path(&self) -> Option<String> {
+            None
+        }
+    }
+
+    fn test_config(name: &str) -> WireGuardConfig {
+        WireGuardConfig {
+            name: name.to_string(),
+            private_key: "test-private-key".to_string(),
+            addresses: vec!["10.0.0.2/24".to_string()],
+            dns: vec![],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![Peer {
+                name: "peer-1".to_string(),
+                public_key: "test-public-key".to_string(),
+                preshared_key: None,
+                endpoint: Some("vpn.example.com:51820".to_string()),
+                allowed_ips: "0.0.0.0/0".to_string(),
+                persistent_keepalive: Some(25),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_connect_and_disconnect_two_tunnels() {
+        let mut manager = TunnelManager::with_platform(Box::new(FakePlatform::new()));
+
+        store::save_config(&test_config("vipn-manager-test-a")).unwrap();
+        store::save_config(&test_config("vipn-manager-test-b")).unwrap();
+
+        manager.connect("vipn-manager-test-a").unwrap();
+        manager.connect("vipn-manager-test-b").unwrap();
+
+        let all = manager.status_all();
+        assert_eq!(all.len(), 2);
+        assert!(all["vipn-manager-test-a"].connected);
+        assert!(all["vipn-manager-test-b"].connected);
+
+        manager.disconnect("vipn-manager-test-a").unwrap();
+        assert_eq!(manager.names(), vec!["vipn-manager-test-b".to_string()]);
+
+        store::delete_config("vipn-manager-test-a").unwrap();
+        store::delete_config("vipn-manager-test-b").unwrap();
+    }
+
+    #[test]
+    fn test_connect_rejects_unknown_name() {
+        let mut manager = TunnelManager::with_platform(Box::new(FakePlatform::new()));
+        let result = manager.connect("vipn-manager-test-does-not-exist");
+        assert!(matches!(result, Err(WireGuardError::ConfigInvalid(_))));
+    }
+
+    #[test]
+    fn test_disconnect_rejects_untracked_name() {
+        let mut manager = TunnelManager::with_platform(Box::new(FakePlatform::new()));
+        let result = manager.disconnect("not-connected");
+        assert!(matches!(result, Err(WireGuardError::InterfaceNotFound(_))));
+    }
+
+    #[test]
+    fn test_connect_profile_resolves_private_key_from_file() {
+        let mut manager = TunnelManager::with_platform(Box::new(FakePlatform::new()));
+
+        let config = test_config("vipn-manager-test-profile");
+        let config_path = std::env::temp_dir().join("vipn-manager-test-profile.conf");
+        std::fs::write(&config_path, crate::wireguard::config_to_wg_quick_format(&config)).unwrap();
+
+        let key_path = std::env::temp_dir().join("vipn-manager-test-profile.key");
+        let _ = std::fs::remove_file(&key_path);
+
+        let profile = crate::wireguard::state::TunnelProfile {
+            name: "vipn-manager-test-profile".to_string(),
+            config_path: config_path.clone(),
+            private_key_file: Some(key_path.clone()),
+            autostart: false,
+        };
+
+        manager.connect_profile(&profile).unwrap();
+        assert!(manager.names().contains(&"vipn-manager-test-profile".to_string()));
+        assert!(key_path.exists());
+
+        std::fs::remove_file(&config_path).unwrap();
+        std::fs::remove_file(&key_path).unwrap();
+    }
+}
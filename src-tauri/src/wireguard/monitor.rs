@@ -0,0 +1,159 @@
+/// Background handshake watchdog.
+///
+/// `apply_config` is one-shot: once a tunnel is up nothing watches it, so a
+/// dead peer or expired handshake goes unnoticed until the next manual status
+/// check. This spawns a task on the existing tokio runtime (mirroring
+/// wireguard-rs's long-running daemon) that polls an interface's status on
+/// an interval, reports every observed [`TunnelHealth`] to a callback, and
+/// reapplies the tunnel's config once the newest handshake goes stale or the
+/// interface drops - turning a boolean `connected` into a self-healing
+/// tunnel.
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::platform::WireGuardPlatform;
+use super::{ConnectionStatus, TunnelHealth, WireGuardConfig};
+
+/// How often to poll, and how stale a handshake can get before the watchdog
+/// reapplies the tunnel's config.
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    pub poll_interval: Duration,
+    pub staleness_threshold: Duration,
+}
+
+impl WatchdogConfig {
+    pub fn new(poll_interval: Duration, staleness_threshold: Duration) -> Self {
+        Self { poll_interval, staleness_threshold }
+    }
+}
+
+impl Default for WatchdogConfig {
+    /// Poll every 10s; reconnect once the handshake is older than the same
+    /// 3x-keepalive default `ConnectionStatus::health` uses.
+    fn default() -> Self {
+        Self { poll_interval: Duration::from_secs(10), staleness_threshold: Duration::from_secs(3 * 25) }
+    }
+}
+
+/// Spawn a task that watches `interface`'s handshake health, calling
+/// `on_health` with every observed [`TunnelHealth`] (a failed status probe is
+/// reported as `Disconnected`), and reapplying `config` whenever health comes
+/// back `Stale` or `Disconnected`. Returns the task's `JoinHandle` so callers
+/// can cancel it (e.g. on `disconnect`).
+pub fn spawn_watchdog(
+    platform: Arc<dyn WireGuardPlatform>,
+    interface: String,
+    config: WireGuardConfig,
+    watchdog: WatchdogConfig,
+    on_health: impl Fn(TunnelHealth) + Send + 'static,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(watchdog.poll_interval).await;
+
+            let status = platform.get_status(Some(&interface)).unwrap_or_else(|_| ConnectionStatus {
+                connected: false,
+                current_config: None,
+                interface: None,
+                peers: vec![],
+            });
+            let health = status.health_with_threshold(watchdog.staleness_threshold);
+            on_health(health);
+
+            if matches!(health, TunnelHealth::Stale | TunnelHealth::Disconnected) {
+                let _ = platform.apply_config(&config);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wireguard::platform::WireGuardError;
+    use crate::wireguard::{Peer, PeerStats};
+    use std::sync::Mutex;
+
+    /// Always reports a peer handshaked once, far in the past, so its health
+    /// is `Stale` under any reasonable threshold - and counts how many times
+    /// `apply_config` gets called as a result.
+    struct AlwaysStalePlatform {
+        reapply_count: Arc<Mutex<usize>>,
+    }
+
+    impl WireGuardPlatform for AlwaysStalePlatform {
+        fn apply_config(&self, _config: &WireGuardConfig) -> Result<String, WireGuardError> {
+            *self.reapply_count.lock().unwrap() += 1;
+            Ok("wg-test".to_string())
+        }
+        fn disconnect(&self, _interface: &str) -> Result<(), WireGuardError> {
+            Ok(())
+        }
+        fn get_status(&self, interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
+            Ok(ConnectionStatus {
+                connected: true,
+                current_config: None,
+                interface: interface.map(String::from),
+                peers: vec![PeerStats {
+                    public_key: "test-public-key".to_string(),
+                    endpoint: None,
+                    allowed_ips: "0.0.0.0/0".to_string(),
+                    last_handshake: Some(std::time::SystemTime::UNIX_EPOCH),
+                    rx_bytes: 0,
+                    tx_bytes: 0,
+                }],
+            })
+        }
+        fn list_interfaces(&self) -> Result<Vec<String>, WireGuardError> {
+            Ok(vec![])
+        }
+        fn is_wireguard_installed(&self) -> bool {
+            true
+        }
+        fn wg_quick_path(&self) -> Option<String> {
+            None
+        }
+    }
+
+    fn test_config() -> WireGuardConfig {
+        WireGuardConfig {
+            name: "vipn-watchdog-test".to_string(),
+            private_key: "test-private-key".to_string(),
+            addresses: vec!["10.0.0.2/24".to_string()],
+            dns: vec![],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![Peer {
+                name: "peer-1".to_string(),
+                public_key: "test-public-key".to_string(),
+                preshared_key: None,
+                endpoint: Some("vpn.example.com:51820".to_string()),
+                allowed_ips: "0.0.0.0/0".to_string(),
+                persistent_keepalive: Some(25),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watchdog_reapplies_config_once_handshake_is_stale() {
+        let reapply_count = Arc::new(Mutex::new(0));
+        let platform: Arc<dyn WireGuardPlatform> =
+            Arc::new(AlwaysStalePlatform { reapply_count: reapply_count.clone() });
+
+        let observed = Arc::new(Mutex::new(Vec::new()));
+        let observed_clone = observed.clone();
+
+        let watchdog = WatchdogConfig::new(Duration::from_millis(10), Duration::from_secs(75));
+        let handle = spawn_watchdog(platform, "wg-test".to_string(), test_config(), watchdog, move |health| {
+            observed_clone.lock().unwrap().push(health);
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.abort();
+
+        assert!(*reapply_count.lock().unwrap() >= 1);
+        assert!(observed.lock().unwrap().iter().all(|h| *h == TunnelHealth::Stale));
+    }
+}
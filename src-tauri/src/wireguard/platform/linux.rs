@@ -1,11 +1,17 @@
 /// Linux-specific WireGuard implementation
 ///
-/// This is a placeholder implementation. The structure mirrors macOS
-/// but uses Linux-specific paths and commands.
-
-use super::{WireGuardError, WireGuardPlatform};
-use crate::wireguard::{WireGuardConfig, ConnectionStatus};
+/// Primary path talks to the kernel directly over netlink: `rtnetlink` for
+/// link/address/route management and the WireGuard generic-netlink family
+/// for device configuration (private key, peers, fwmark). This avoids
+/// spawning `wg-quick`, so it works without the WireGuard CLI tools
+/// installed and is atomic/synchronous rather than shelling out. When the
+/// calling process doesn't have the netlink permissions it needs (e.g. no
+/// `CAP_NET_ADMIN`), we fall back to the wg-quick backend.
+use super::{BackendInfo, BackendKind, WireGuardError, WireGuardPlatform};
+use crate::wireguard::{config_to_wg_quick_format, ConnectionStatus, WireGuardConfig};
+use std::io::Write;
 use std::process::Command;
+use wireguard_uapi::{DeviceInterface, WgSocket};
 
 pub struct LinuxPlatform;
 
@@ -15,13 +21,8 @@ impl LinuxPlatform {
     }
 
     fn find_wg_quick(&self) -> Option<String> {
-        // Common locations on Linux
-        let paths = vec![
-            "/usr/bin/wg-quick",
-            "/usr/local/bin/wg-quick",
-        ];
+        let paths = vec!["/usr/bin/wg-quick", "/usr/local/bin/wg-quick"];
 
-        // Check PATH
         if let Ok(output) = Command::new("which").arg("wg-quick").output() {
             if output.status.success() {
                 if let Ok(path) = String::from_utf8(output.stdout) {
@@ -30,7 +31,6 @@ impl LinuxPlatform {
             }
         }
 
-        // Check common paths
         for path in paths {
             if std::path::PathBuf::from(&path).exists() {
                 return Some(path);
@@ -39,35 +39,303 @@ impl LinuxPlatform {
 
         None
     }
+
+    /// Whether this process can open the generic-netlink WireGuard family
+    /// socket, i.e. whether the netlink backend is usable at all.
+    fn netlink_available(&self) -> bool {
+        WgSocket::connect().is_ok()
+    }
+
+    /// Derive a kernel-safe interface name from the config's name, the way
+    /// `WindowsPlatform::write_tunnel_config` derives its tunnel name.
+    /// Interface names are capped at IFNAMSIZ - 1 = 15 bytes by the kernel,
+    /// so non-alphanumeric characters are replaced and the result is
+    /// truncated to fit; two configs that sanitize to the same name would
+    /// still collide, but an empty/all-symbol name no longer collapses every
+    /// tunnel onto a shared `"wg0"`.
+    fn generate_interface_name(&self, config_name: &str) -> String {
+        let sanitized: String =
+            config_name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).take(15).collect();
+
+        if sanitized.is_empty() {
+            "wg0".to_string()
+        } else {
+            sanitized
+        }
+    }
+
+    /// Install `wireguard-tools` via whichever supported package manager is
+    /// on PATH, preferring apt (Debian/Ubuntu) then dnf (Fedora/RHEL).
+    fn install_wireguard_tools(&self) -> Result<(), WireGuardError> {
+        for manager in ["apt-get", "dnf"] {
+            let found = Command::new("which").arg(manager).output().map(|o| o.status.success()).unwrap_or(false);
+            if !found {
+                continue;
+            }
+
+            let output = Command::new(manager)
+                .args(["install", "-y", "wireguard-tools"])
+                .output()
+                .map_err(|e| WireGuardError::InstallFailed(format!("Failed to run {}: {}", manager, e)))?;
+
+            if !output.status.success() {
+                return Err(WireGuardError::InstallFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+            }
+
+            return Ok(());
+        }
+
+        Err(WireGuardError::InstallFailed("No supported package manager (apt-get, dnf) found on PATH".to_string()))
+    }
+
+    /// Parse the version out of `wg --version`'s `wireguard-tools vX.Y.Z` output.
+    fn wg_tools_version(&self) -> Option<String> {
+        let output = Command::new("wg").arg("--version").output().ok()?;
+        String::from_utf8_lossy(&output.stdout).split_whitespace().nth(1).map(str::to_string)
+    }
+
+    /// Create (if needed) the device, push its configuration over netlink,
+    /// assign the interface address, and bring it up.
+    fn apply_via_netlink(&self, config: &WireGuardConfig, interface: &str) -> Result<(), WireGuardError> {
+        let mut wg = WgSocket::connect()
+            .map_err(|_| WireGuardError::PermissionDenied)?;
+
+        let mut rt = rtnetlink::new_connection()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to open rtnetlink: {}", e)))?;
+
+        rt.handle()
+            .link()
+            .add_wireguard(interface.to_string())
+            .execute()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to create {}: {}", interface, e)))?;
+
+        for address in &config.addresses {
+            rt.handle()
+                .address()
+                .add_from_cidr(interface, address)
+                .execute()
+                .map_err(|e| WireGuardError::CommandFailed(format!("Failed to assign address {}: {}", address, e)))?;
+        }
+
+        if let Some(mtu) = config.mtu {
+            rt.handle()
+                .link()
+                .set_mtu(interface, mtu)
+                .execute()
+                .map_err(|e| WireGuardError::CommandFailed(format!("Failed to set MTU: {}", e)))?;
+        }
+
+        let mut device = DeviceInterface::from_name(interface).private_key(&config.private_key);
+        if let Some(fwmark) = config.fwmark {
+            device = device.fwmark(fwmark);
+        }
+        if let Some(listen_port) = config.listen_port {
+            device = device.listen_port(listen_port);
+        }
+        for peer in &config.peers {
+            device = device.peer(&peer.public_key, peer.endpoint.as_deref(), &peer.allowed_ips, peer.persistent_keepalive);
+            if let Some(preshared_key) = &peer.preshared_key {
+                device = device.peer_preshared_key(&peer.public_key, preshared_key);
+            }
+        }
+
+        wg.set_device(device)
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to configure device: {}", e)))?;
+
+        rt.handle()
+            .link()
+            .set_up(interface)
+            .execute()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to bring {} up: {}", interface, e)))?;
+
+        Ok(())
+    }
+
+    fn execute_wg_quick(&self, args: &[&str]) -> Result<String, WireGuardError> {
+        let wg_quick = self.find_wg_quick().ok_or(WireGuardError::NotInstalled)?;
+
+        let output = Command::new(&wg_quick)
+            .args(args)
+            .output()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to execute wg-quick: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(WireGuardError::CommandFailed(stderr.to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn apply_via_wg_quick(&self, config: &WireGuardConfig) -> Result<String, WireGuardError> {
+        let interface = self.generate_interface_name(&config.name);
+        let wg_format = config_to_wg_quick_format(config);
+
+        // wg-quick derives the interface it creates from the config file's
+        // basename, so the file must be named exactly `<interface>.conf` -
+        // not an arbitrary temp name - or the interface we return here won't
+        // match the one wg-quick actually brought up.
+        let mut temp_file = tempfile::Builder::new()
+            .prefix(&interface)
+            .suffix(".conf")
+            .rand_bytes(0)
+            .tempfile()
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to create temp file: {}", e)))?;
+        temp_file
+            .write_all(wg_format.as_bytes())
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to write config: {}", e)))?;
+        let path = temp_file.path().to_path_buf();
+        temp_file
+            .keep()
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to persist temp file: {}", e)))?;
+
+        self.execute_wg_quick(&["up", path.to_str().unwrap()])?;
+
+        Ok(interface)
+    }
 }
 
 impl WireGuardPlatform for LinuxPlatform {
-    fn apply_config(&self, _config: &WireGuardConfig) -> Result<String, WireGuardError> {
-        // TODO: Implement Linux-specific logic
-        Err(WireGuardError::PlatformNotSupported)
+    fn apply_config(&self, config: &WireGuardConfig) -> Result<String, WireGuardError> {
+        let interface = self.generate_interface_name(&config.name);
+
+        if self.netlink_available() {
+            self.apply_via_netlink(config, &interface)?;
+            return Ok(interface);
+        }
+
+        self.apply_via_wg_quick(config)
     }
 
-    fn disconnect(&self, _interface: &str) -> Result<(), WireGuardError> {
-        // TODO: Implement Linux-specific logic
-        Err(WireGuardError::PlatformNotSupported)
+    fn disconnect(&self, interface: &str) -> Result<(), WireGuardError> {
+        if self.netlink_available() {
+            let mut rt = rtnetlink::new_connection()
+                .map_err(|e| WireGuardError::CommandFailed(format!("Failed to open rtnetlink: {}", e)))?;
+
+            rt.handle()
+                .link()
+                .del(interface)
+                .execute()
+                .map_err(|e| WireGuardError::CommandFailed(format!("Failed to remove {}: {}", interface, e)))?;
+
+            return Ok(());
+        }
+
+        let config_paths = vec![
+            format!("/etc/wireguard/{}.conf", interface),
+            format!("{}/.config/wireguard/{}.conf", std::env::var("HOME").unwrap_or_default(), interface),
+        ];
+
+        for config_path in config_paths {
+            if std::path::PathBuf::from(&config_path).exists() {
+                self.execute_wg_quick(&["down", &config_path])?;
+                return Ok(());
+            }
+        }
+
+        self.execute_wg_quick(&["down", interface])?;
+        Ok(())
     }
 
-    fn get_status(&self, _interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
-        // TODO: Implement Linux-specific logic
-        Err(WireGuardError::PlatformNotSupported)
+    fn get_status(&self, interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
+        let interfaces = if let Some(iface) = interface {
+            vec![iface.to_string()]
+        } else {
+            self.list_interfaces()?
+        };
+
+        if interfaces.is_empty() {
+            return Ok(ConnectionStatus {
+                connected: false,
+                current_config: None,
+                interface: None,
+                peers: vec![],
+            });
+        }
+
+        if self.netlink_available() {
+            let mut wg = WgSocket::connect()
+                .map_err(|_| WireGuardError::PermissionDenied)?;
+            let device = wg
+                .get_device(DeviceInterface::from_name(&interfaces[0]))
+                .map_err(|e| WireGuardError::InterfaceNotFound(format!("{}: {}", interfaces[0], e)))?;
+
+            let peers = device.peers_as_wg_dump_lines().iter().map(String::as_str).collect::<Vec<_>>().join("\n");
+            let dump_peers = super::parse_wg_dump(&format!("\n{}", peers));
+            let is_connected = dump_peers.iter().any(|p| p.last_handshake.is_some());
+
+            return Ok(ConnectionStatus {
+                connected: is_connected,
+                current_config: if is_connected { Some(interfaces[0].clone()) } else { None },
+                interface: if is_connected { Some(interfaces[0].clone()) } else { None },
+                peers: dump_peers,
+            });
+        }
+
+        let output = Command::new("wg")
+            .args(["show", &interfaces[0], "dump"])
+            .output()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to check status: {}", e)))?;
+
+        let dump = String::from_utf8_lossy(&output.stdout);
+        let peers = super::parse_wg_dump(&dump);
+        let is_connected = output.status.success() && peers.iter().any(|p| p.last_handshake.is_some());
+
+        Ok(ConnectionStatus {
+            connected: is_connected,
+            current_config: if is_connected { Some(interfaces[0].clone()) } else { None },
+            interface: if is_connected { Some(interfaces[0].clone()) } else { None },
+            peers,
+        })
     }
 
     fn list_interfaces(&self) -> Result<Vec<String>, WireGuardError> {
-        // TODO: Implement Linux-specific logic
-        Ok(vec![])
+        if self.netlink_available() {
+            let mut wg = WgSocket::connect()
+                .map_err(|_| WireGuardError::PermissionDenied)?;
+            let names = wg
+                .list_device_names()
+                .map_err(|e| WireGuardError::CommandFailed(format!("Failed to list interfaces: {}", e)))?;
+            return Ok(names);
+        }
+
+        let output = Command::new("wg")
+            .arg("show")
+            .output()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to list interfaces: {}", e)))?;
+
+        if !output.status.success() {
+            return Ok(vec![]);
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect())
     }
 
     fn is_wireguard_installed(&self) -> bool {
-        self.find_wg_quick().is_some()
+        self.netlink_available() || self.find_wg_quick().is_some()
     }
 
     fn wg_quick_path(&self) -> Option<String> {
         self.find_wg_quick()
     }
-}
 
+    /// Install `wireguard-tools` if missing, then load the kernel module so
+    /// the netlink backend is usable without a reboot, falling back to
+    /// reporting the userspace backend if the module can't be loaded.
+    fn ensure_installed(&self) -> Result<BackendInfo, WireGuardError> {
+        if !self.is_wireguard_installed() {
+            self.install_wireguard_tools()?;
+        }
+
+        let _ = Command::new("modprobe").arg("wireguard").output();
+
+        let kind = if self.netlink_available() || self.find_wg_quick().is_some() {
+            BackendKind::Kernel
+        } else {
+            BackendKind::UserspaceGo
+        };
+
+        Ok(BackendInfo { kind, tools_version: self.wg_tools_version(), wg_quick_path: self.find_wg_quick() })
+    }
+}
@@ -1,5 +1,5 @@
 /// macOS-specific WireGuard implementation
-use super::{WireGuardError, WireGuardPlatform};
+use super::{BackendInfo, BackendKind, WireGuardError, WireGuardPlatform};
 use crate::wireguard::{WireGuardConfig, ConnectionStatus};
 use std::process::Command;
 use std::path::PathBuf;
@@ -43,6 +43,13 @@ impl MacOSPlatform {
         None
     }
 
+    /// Parse the version out of `wg --version`'s `wireguard-tools vX.Y.Z` output.
+    fn wg_tools_version(&self) -> Option<String> {
+        let wg_path = self.find_wg_quick()?.replace("wg-quick", "wg");
+        let output = Command::new(&wg_path).arg("--version").output().ok()?;
+        String::from_utf8_lossy(&output.stdout).split_whitespace().nth(1).map(str::to_string)
+    }
+
     /// Execute wg-quick command
     fn execute_wg_quick(&self, args: &[&str]) -> Result<String, WireGuardError> {
         let wg_quick = self.find_wg_quick()
@@ -101,15 +108,24 @@ impl WireGuardPlatform for MacOSPlatform {
         // Execute: wg-quick up <config_file>
         self.execute_wg_quick(&["up", config_path.to_str().unwrap()])?;
 
+        // Remember exactly which config file this interface came from, so
+        // disconnect() doesn't have to guess at a path.
+        let mut state = crate::wireguard::state::TunnelState::load()?;
+        state.record_active(&config.name, &interface, config_path)?;
+
         Ok(interface)
     }
 
     fn disconnect(&self, interface: &str) -> Result<(), WireGuardError> {
-        // Find the config file for this interface
-        // For now, we'll use a simple approach: wg-quick down <interface>
-        // In a real implementation, we'd track which config file was used
+        let mut state = crate::wireguard::state::TunnelState::load()?;
+
+        if let Some(active) = state.clear_active(interface)? {
+            self.execute_wg_quick(&["down", active.config_path.to_str().unwrap()])?;
+            return Ok(());
+        }
 
-        // Try to find config file in common locations
+        // No recorded state (e.g. tunnel brought up outside this app) - fall
+        // back to the old probing behavior before giving up.
         let config_paths = vec![
             format!("/etc/wireguard/{}.conf", interface),
             format!("{}/.config/wireguard/{}.conf", std::env::var("HOME").unwrap_or_default(), interface),
@@ -122,8 +138,6 @@ impl WireGuardPlatform for MacOSPlatform {
             }
         }
 
-        // If no config file found, try direct interface down
-        // Note: This might not work on all systems
         self.execute_wg_quick(&["down", interface])?;
         Ok(())
     }
@@ -145,6 +159,7 @@ impl WireGuardPlatform for MacOSPlatform {
                 connected: false,
                 current_config: None,
                 interface: None,
+                peers: vec![],
             });
         }
 
@@ -154,12 +169,26 @@ impl WireGuardPlatform for MacOSPlatform {
             .output()
             .map_err(|e| WireGuardError::CommandFailed(format!("Failed to check status: {}", e)))?;
 
-        let is_connected = output.status.success() && !output.stdout.is_empty();
+        let dump = String::from_utf8_lossy(&output.stdout);
+        let peers = super::parse_wg_dump(&dump);
+
+        // A non-empty dump only means the interface exists; only a peer
+        // with a real (non-zero) handshake means the tunnel is connected.
+        let is_connected = output.status.success() && peers.iter().any(|p| p.last_handshake.is_some());
+
+        // Reconcile our recorded state against reality: if the interface
+        // isn't actually up anymore, drop any stale "active" record for it.
+        if !output.status.success() {
+            if let Ok(mut state) = crate::wireguard::state::TunnelState::load() {
+                let _ = state.clear_active(&interfaces[0]);
+            }
+        }
 
         Ok(ConnectionStatus {
             connected: is_connected,
             current_config: if is_connected { Some(interfaces[0].clone()) } else { None },
             interface: if is_connected { Some(interfaces[0].clone()) } else { None },
+            peers,
         })
     }
 
@@ -201,5 +230,22 @@ impl WireGuardPlatform for MacOSPlatform {
     fn wg_quick_path(&self) -> Option<String> {
         self.find_wg_quick()
     }
+
+    /// Install `wireguard-tools` via Homebrew if it isn't already present.
+    fn ensure_installed(&self) -> Result<BackendInfo, WireGuardError> {
+        if !self.is_wireguard_installed() {
+            let output = Command::new("brew")
+                .args(["install", "wireguard-tools"])
+                .output()
+                .map_err(|e| WireGuardError::InstallFailed(format!("Failed to run brew: {}", e)))?;
+
+            if !output.status.success() {
+                return Err(WireGuardError::InstallFailed(String::from_utf8_lossy(&output.stderr).to_string()));
+            }
+        }
+
+        let wg_quick_path = self.find_wg_quick().ok_or(WireGuardError::NotInstalled)?;
+        Ok(BackendInfo { kind: BackendKind::Kernel, tools_version: self.wg_tools_version(), wg_quick_path: Some(wg_quick_path) })
+    }
 }
 
@@ -3,7 +3,9 @@
 /// This module provides a trait-based abstraction for WireGuard operations
 /// that can be implemented differently on each platform.
 
-use crate::wireguard::{WireGuardConfig, ConnectionStatus};
+use crate::wireguard::{WireGuardConfig, ConnectionStatus, PeerStats};
+use std::process::Command;
+use std::time::{Duration, Instant, UNIX_EPOCH};
 
 /// Errors that can occur during WireGuard operations
 #[derive(Debug, Clone)]
@@ -15,6 +17,12 @@ pub enum WireGuardError {
     CommandFailed(String),
     NetworkError(String),
     PlatformNotSupported,
+    /// None of the configured `ReadinessProbe`s succeeded before the
+    /// deadline passed in `ReadinessConfig::timeout`.
+    ReadinessTimeout,
+    /// `ensure_installed` tried to install WireGuard tooling via the
+    /// platform's package manager and the install itself failed.
+    InstallFailed(String),
 }
 
 impl std::fmt::Display for WireGuardError {
@@ -27,6 +35,8 @@ impl std::fmt::Display for WireGuardError {
             WireGuardError::CommandFailed(msg) => write!(f, "Command failed: {}", msg),
             WireGuardError::NetworkError(msg) => write!(f, "Network error: {}", msg),
             WireGuardError::PlatformNotSupported => write!(f, "Platform not supported"),
+            WireGuardError::ReadinessTimeout => write!(f, "Tunnel did not become ready before the readiness timeout"),
+            WireGuardError::InstallFailed(msg) => write!(f, "Failed to install WireGuard tooling: {}", msg),
         }
     }
 }
@@ -53,29 +63,261 @@ pub trait WireGuardPlatform: Send + Sync {
 
     /// Get the path to wg-quick command
     fn wg_quick_path(&self) -> Option<String>;
+
+    /// Block until `readiness`'s probes all succeed, or return
+    /// `Err(ReadinessTimeout)` once `readiness.timeout` elapses. The default
+    /// implementation only depends on [`ReadinessProbe::check`], not on any
+    /// platform internals, so it covers every backend; override it if a
+    /// platform can confirm readiness more directly (e.g. reading handshake
+    /// state straight from the kernel instead of shelling out).
+    fn wait_until_ready(&self, _interface: &str, readiness: &ReadinessConfig) -> Result<(), WireGuardError> {
+        let deadline = Instant::now() + readiness.timeout;
+
+        loop {
+            if readiness.probes.iter().all(ReadinessProbe::check) {
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(WireGuardError::ReadinessTimeout);
+            }
+
+            std::thread::sleep(readiness.retry_interval);
+        }
+    }
+
+    /// Install WireGuard tooling via the platform's package manager if
+    /// `is_wireguard_installed` is false, then report which backend is
+    /// actually usable. The default implementation has no package manager to
+    /// call out to, so it only reports the current state; platforms with one
+    /// (macOS/Homebrew, Linux/apt-dnf) override this to actually install.
+    fn ensure_installed(&self) -> Result<BackendInfo, WireGuardError> {
+        if !self.is_wireguard_installed() {
+            return Err(WireGuardError::InstallFailed(
+                "WireGuard tooling is not installed and this platform has no automatic installer".to_string(),
+            ));
+        }
+
+        let kind = if self.wg_quick_path().is_some() { BackendKind::Kernel } else { BackendKind::UserspaceGo };
+        Ok(BackendInfo { kind, tools_version: None, wg_quick_path: self.wg_quick_path() })
+    }
+}
+
+/// Which underlying WireGuard implementation a platform backend actually
+/// uses, as reported by `ensure_installed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    /// The OS's native in-kernel WireGuard module (or wg-quick driving it).
+    Kernel,
+    /// A userspace implementation (wireguard-go / boringtun) over its UAPI.
+    UserspaceGo,
 }
 
-/// Create a platform-specific WireGuard implementation
+/// What `ensure_installed` found (or installed) on this machine.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackendInfo {
+    pub kind: BackendKind,
+    pub tools_version: Option<String>,
+    pub wg_quick_path: Option<String>,
+}
+
+/// A single connectivity check used to confirm a tunnel is actually usable,
+/// not just that the interface exists. Modeled on cloud-init's WireGuard
+/// readiness checks.
+#[derive(Debug, Clone)]
+pub enum ReadinessProbe {
+    /// Ping a peer's tunnel IP `count` times via the system `ping` binary.
+    Ping { target: String, count: u32 },
+    /// Resolve a hostname, e.g. one only reachable via the tunnel's DNS.
+    ResolveHostname { hostname: String },
+    /// HTTP GET a URL via the system `curl` binary and expect a 2xx status.
+    HttpGet { url: String },
+}
+
+impl ReadinessProbe {
+    /// Run the probe once. `true` means it succeeded; any failure (command
+    /// missing, non-2xx, unresolvable host, ...) is folded into `false` so
+    /// callers just retry rather than distinguishing failure causes.
+    fn check(&self) -> bool {
+        match self {
+            ReadinessProbe::Ping { target, count } => {
+                // Windows' ping.exe takes `-n` for the repeat count; every
+                // other `ping` (GNU/BSD/macOS) takes `-c`.
+                let count_flag = if cfg!(target_os = "windows") { "-n" } else { "-c" };
+                Command::new("ping")
+                    .arg(count_flag)
+                    .arg(count.to_string())
+                    .arg(target)
+                    .output()
+                    .map(|o| o.status.success())
+                    .unwrap_or(false)
+            }
+            ReadinessProbe::ResolveHostname { hostname } => {
+                use std::net::ToSocketAddrs;
+                (hostname.as_str(), 0u16).to_socket_addrs().map(|mut addrs| addrs.next().is_some()).unwrap_or(false)
+            }
+            ReadinessProbe::HttpGet { url } => Command::new("curl")
+                .args(["-fsS", "-o", "/dev/null", url])
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Readiness probes to run (with retry/backoff) before considering a tunnel
+/// up, plus the deadline after which `wait_until_ready` gives up.
+#[derive(Debug, Clone)]
+pub struct ReadinessConfig {
+    pub probes: Vec<ReadinessProbe>,
+    pub timeout: Duration,
+    pub retry_interval: Duration,
+}
+
+impl ReadinessConfig {
+    pub fn new(probes: Vec<ReadinessProbe>, timeout: Duration) -> Self {
+        Self { probes, timeout, retry_interval: Duration::from_millis(500) }
+    }
+}
+
+/// Apply a config, then (if `readiness` is given) block until its probes
+/// confirm the tunnel is actually carrying traffic, not just that the
+/// interface was created. Returns `Err(ReadinessTimeout)` rather than the
+/// interface name if readiness isn't confirmed in time.
+pub fn apply_config_with_readiness(
+    platform: &dyn WireGuardPlatform,
+    config: &WireGuardConfig,
+    readiness: Option<&ReadinessConfig>,
+) -> Result<String, WireGuardError> {
+    let interface = platform.apply_config(config)?;
+
+    if let Some(readiness) = readiness {
+        platform.wait_until_ready(&interface, readiness)?;
+    }
+
+    Ok(interface)
+}
+
+/// Parse the tab-separated output of `wg show <iface> dump` into per-peer
+/// statistics. The first line describes the interface itself (private key,
+/// public key, listen port, fwmark) and is skipped; every following line is
+/// one peer: public key, preshared key, endpoint, allowed ips, latest
+/// handshake (unix seconds), rx bytes, tx bytes, persistent keepalive.
+///
+/// A `latest-handshake` of `0` means no handshake has happened yet, so it's
+/// surfaced as `None` rather than a `SystemTime` at the epoch.
+pub fn parse_wg_dump(dump: &str) -> Vec<PeerStats> {
+    dump.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 8 {
+                return None;
+            }
+
+            let public_key = fields[0].to_string();
+            let endpoint = match fields[2] {
+                "(none)" | "" => None,
+                endpoint => Some(endpoint.to_string()),
+            };
+            let allowed_ips = fields[3].to_string();
+            let last_handshake = fields[4].parse::<u64>().ok().filter(|&secs| secs != 0)
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+            let rx_bytes = fields[5].parse().unwrap_or(0);
+            let tx_bytes = fields[6].parse().unwrap_or(0);
+
+            Some(PeerStats {
+                public_key,
+                endpoint,
+                allowed_ips,
+                last_handshake,
+                rx_bytes,
+                tx_bytes,
+            })
+        })
+        .collect()
+}
+
+/// Which WireGuard backend to prefer when creating a platform implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendPreference {
+    /// Use the OS's native kernel module / wg-quick tooling.
+    Kernel,
+    /// Use a userspace tunnel (wireguard-go / boringtun) over its UAPI socket.
+    Userspace,
+}
+
+/// Create a platform-specific WireGuard implementation, preferring the
+/// native kernel/wg-quick backend.
 pub fn create_platform() -> Result<Box<dyn WireGuardPlatform>, WireGuardError> {
+    create_platform_with_preference(BackendPreference::Kernel)
+}
+
+/// Create a platform-specific WireGuard implementation for the given
+/// preference, falling back to the userspace backend when the native
+/// tooling isn't installed.
+pub fn create_platform_with_preference(
+    preference: BackendPreference,
+) -> Result<Box<dyn WireGuardPlatform>, WireGuardError> {
+    if preference == BackendPreference::Userspace {
+        return Ok(Box::new(crate::wireguard::platform::userspace::UserspacePlatform::new()));
+    }
+
     #[cfg(target_os = "macos")]
     {
-        Ok(Box::new(crate::wireguard::platform::macos::MacOSPlatform::new()))
+        let native = crate::wireguard::platform::macos::MacOSPlatform::new();
+        if native.is_wireguard_installed() {
+            return Ok(Box::new(native));
+        }
     }
 
     #[cfg(target_os = "linux")]
     {
-        Ok(Box::new(crate::wireguard::platform::linux::LinuxPlatform::new()))
+        let native = crate::wireguard::platform::linux::LinuxPlatform::new();
+        if native.is_wireguard_installed() {
+            return Ok(Box::new(native));
+        }
     }
 
     #[cfg(target_os = "windows")]
     {
-        Ok(Box::new(crate::wireguard::platform::windows::WindowsPlatform::new()))
+        let native = crate::wireguard::platform::windows::WindowsPlatform::new();
+        if native.is_wireguard_installed() {
+            return Ok(Box::new(native));
+        }
+    }
+
+    let userspace = crate::wireguard::platform::userspace::UserspacePlatform::new();
+    if userspace.is_wireguard_installed() {
+        return Ok(Box::new(userspace));
     }
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
-        Err(WireGuardError::PlatformNotSupported)
+        return Err(WireGuardError::PlatformNotSupported);
     }
+
+    #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+    Err(WireGuardError::NotInstalled)
+}
+
+/// Create the OS's native platform implementation regardless of whether its
+/// tooling is currently installed, so `ensure_installed` has something to
+/// install onto. Falls back to the userspace backend on platforms with no
+/// native implementation of their own.
+pub fn create_native_platform() -> Box<dyn WireGuardPlatform> {
+    #[cfg(target_os = "macos")]
+    return Box::new(crate::wireguard::platform::macos::MacOSPlatform::new());
+
+    #[cfg(target_os = "linux")]
+    return Box::new(crate::wireguard::platform::linux::LinuxPlatform::new());
+
+    #[cfg(target_os = "windows")]
+    return Box::new(crate::wireguard::platform::windows::WindowsPlatform::new());
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    Box::new(crate::wireguard::platform::userspace::UserspacePlatform::new())
 }
 
 // Platform-specific implementations
@@ -88,3 +330,114 @@ pub mod linux;
 #[cfg(target_os = "windows")]
 pub mod windows;
 
+pub mod userspace;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_hostname_probe_succeeds_for_localhost() {
+        let probe = ReadinessProbe::ResolveHostname { hostname: "localhost".to_string() };
+        assert!(probe.check());
+    }
+
+    #[test]
+    fn test_resolve_hostname_probe_fails_for_bogus_name() {
+        let probe = ReadinessProbe::ResolveHostname { hostname: "this.does.not.resolve.invalid".to_string() };
+        assert!(!probe.check());
+    }
+
+    struct AlwaysFailsPlatform;
+
+    impl WireGuardPlatform for AlwaysFailsPlatform {
+        fn apply_config(&self, _config: &WireGuardConfig) -> Result<String, WireGuardError> {
+            Ok("wg-test".to_string())
+        }
+        fn disconnect(&self, _interface: &str) -> Result<(), WireGuardError> {
+            Ok(())
+        }
+        fn get_status(&self, _interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
+            Ok(ConnectionStatus { connected: false, current_config: None, interface: None, peers: vec![] })
+        }
+        fn list_interfaces(&self) -> Result<Vec<String>, WireGuardError> {
+            Ok(vec![])
+        }
+        fn is_wireguard_installed(&self) -> bool {
+            true
+        }
+        fn wg_quick_path(&self) -> Option<String> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_wait_until_ready_times_out_when_probes_never_pass() {
+        let platform = AlwaysFailsPlatform;
+        let readiness = ReadinessConfig {
+            probes: vec![ReadinessProbe::ResolveHostname { hostname: "this.does.not.resolve.invalid".to_string() }],
+            timeout: Duration::from_millis(50),
+            retry_interval: Duration::from_millis(10),
+        };
+
+        let result = platform.wait_until_ready("wg-test", &readiness);
+        assert!(matches!(result, Err(WireGuardError::ReadinessTimeout)));
+    }
+
+    #[test]
+    fn test_apply_config_with_readiness_succeeds_with_no_readiness_config() {
+        let platform = AlwaysFailsPlatform;
+        let config = WireGuardConfig {
+            name: "test".to_string(),
+            private_key: "test-key".to_string(),
+            addresses: vec!["10.0.0.2/24".to_string()],
+            dns: vec![],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![],
+        };
+
+        let result = apply_config_with_readiness(&platform, &config, None);
+        assert_eq!(result.unwrap(), "wg-test");
+    }
+
+    #[test]
+    fn test_ensure_installed_default_reports_userspace_without_wg_quick() {
+        let platform = AlwaysFailsPlatform;
+        // AlwaysFailsPlatform::wg_quick_path returns None, so this exercises
+        // the userspace branch of the default ensure_installed.
+        let info = platform.ensure_installed().unwrap();
+        assert_eq!(info.kind, BackendKind::UserspaceGo);
+    }
+
+    struct NeverInstalledPlatform;
+
+    impl WireGuardPlatform for NeverInstalledPlatform {
+        fn apply_config(&self, _config: &WireGuardConfig) -> Result<String, WireGuardError> {
+            Err(WireGuardError::NotInstalled)
+        }
+        fn disconnect(&self, _interface: &str) -> Result<(), WireGuardError> {
+            Ok(())
+        }
+        fn get_status(&self, _interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
+            Ok(ConnectionStatus { connected: false, current_config: None, interface: None, peers: vec![] })
+        }
+        fn list_interfaces(&self) -> Result<Vec<String>, WireGuardError> {
+            Ok(vec![])
+        }
+        fn is_wireguard_installed(&self) -> bool {
+            false
+        }
+        fn wg_quick_path(&self) -> Option<String> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_ensure_installed_default_fails_without_installer() {
+        let platform = NeverInstalledPlatform;
+        let result = platform.ensure_installed();
+        assert!(matches!(result, Err(WireGuardError::InstallFailed(_))));
+    }
+}
@@ -7,7 +7,7 @@
 mod integration_tests {
     use super::super::{WireGuardPlatform, WireGuardError};
     use crate::wireguard::platform::{create_platform, macos::MacOSPlatform};
-    use crate::wireguard::{WireGuardConfig, ConnectionStatus};
+    use crate::wireguard::{WireGuardConfig, ConnectionStatus, Peer};
 
     /// Test fixture: Create a valid test WireGuard config
     /// Note: This uses test keys - NOT for production use
@@ -16,13 +16,20 @@ mod integration_tests {
             name: name.to_string(),
             // Test private key (base64 encoded, 32 bytes)
             private_key: "cF7B1i7pHVXo0jRGyTqNy5GZgQWQ6Y5vN8jH9kL2mP3q=".to_string(),
-            // Test public key (corresponding to above private key)
-            public_key: "xTk2K9vN8jH9kL2mP3qRGyTqNy5GZgQWQ6Y5vN8jH=".to_string(),
-            endpoint: "127.0.0.1:51820".to_string(), // Localhost for testing
-            allowed_ips: "10.0.0.0/8".to_string(), // Narrow scope for testing
-            dns: Some("1.1.1.1".to_string()),
-            address: "10.0.0.2/24".to_string(),
-            persistent_keepalive: Some(25),
+            dns: vec!["1.1.1.1".to_string()],
+            addresses: vec!["10.0.0.2/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![Peer {
+                name: "test-peer".to_string(),
+                // Test public key (corresponding to the private key above)
+                public_key: "xTk2K9vN8jH9kL2mP3qRGyTqNy5GZgQWQ6Y5vN8jH=".to_string(),
+                preshared_key: None,
+                endpoint: Some("127.0.0.1:51820".to_string()), // Localhost for testing
+                allowed_ips: "10.0.0.0/8".to_string(), // Narrow scope for testing
+                persistent_keepalive: Some(25),
+            }],
         }
     }
 
@@ -85,9 +92,9 @@ mod integration_tests {
         assert!(wg_format.contains("[Interface]"));
         assert!(wg_format.contains("[Peer]"));
         assert!(wg_format.contains(&config.private_key));
-        assert!(wg_format.contains(&config.public_key));
-        assert!(wg_format.contains(&config.endpoint));
-        assert!(wg_format.contains(&config.address));
+        assert!(wg_format.contains(&config.peers[0].public_key));
+        assert!(wg_format.contains(config.peers[0].endpoint.as_ref().unwrap()));
+        assert!(wg_format.contains(&config.addresses[0]));
     }
 
     /// Integration Test: Apply config (requires WireGuard and may need sudo)
@@ -179,6 +186,7 @@ mod integration_tests {
             connected: false,
             current_config: None,
             interface: None,
+            peers: vec![],
         });
 
         // Interface may still exist but should be down
@@ -217,12 +225,19 @@ mod integration_tests {
         let invalid_config = WireGuardConfig {
             name: "invalid".to_string(),
             private_key: "".to_string(), // Invalid: empty key
-            public_key: "invalid-key".to_string(),
-            endpoint: "not-a-valid-endpoint".to_string(),
-            allowed_ips: "0.0.0.0/0".to_string(),
-            dns: None,
-            address: "10.0.0.1/24".to_string(),
-            persistent_keepalive: None,
+            dns: vec![],
+            addresses: vec!["10.0.0.1/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![Peer {
+                name: "peer".to_string(),
+                public_key: "invalid-key".to_string(),
+                preshared_key: None,
+                endpoint: Some("not-a-valid-endpoint".to_string()),
+                allowed_ips: "0.0.0.0/0".to_string(),
+                persistent_keepalive: None,
+            }],
         };
 
         let result = platform.apply_config(&invalid_config);
@@ -0,0 +1,377 @@
+/// Userspace WireGuard backend (wireguard-go / boringtun)
+///
+/// Used as a fallback on systems without a kernel WireGuard module or
+/// wg-quick installed (e.g. containers, locked-down machines). Spawns a
+/// userspace tunnel process and configures it over its UAPI unix socket
+/// using the standard `set`/`get` text protocol described at
+/// https://www.wireguard.com/xplatform/.
+use super::{WireGuardError, WireGuardPlatform};
+use crate::wireguard::{ConnectionStatus, PeerStats, WireGuardConfig};
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::process::{Command, Stdio};
+use std::time::{Duration, UNIX_EPOCH};
+
+pub struct UserspacePlatform;
+
+impl UserspacePlatform {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Locate the userspace tunnel binary, preferring `wireguard-go`.
+    fn find_userspace_binary(&self) -> Option<String> {
+        for name in ["wireguard-go", "boringtun"] {
+            if let Ok(output) = Command::new("which").arg(name).output() {
+                if output.status.success() {
+                    if let Ok(path) = String::from_utf8(output.stdout) {
+                        return Some(path.trim().to_string());
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn uapi_socket_path(interface: &str) -> String {
+        format!("/var/run/wireguard/{}.sock", interface)
+    }
+
+    /// Derive a tunnel name per config, the way
+    /// `LinuxPlatform::generate_interface_name` does, instead of a constant
+    /// - otherwise a second concurrently-applied config resolves to the same
+    /// UAPI socket as the first and `ensure_tunnel_process` just reconfigures
+    /// the already-running tunnel instead of starting a second one.
+    fn generate_interface_name(&self, config_name: &str) -> String {
+        let sanitized: String =
+            config_name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+
+        if sanitized.is_empty() {
+            "utun-wg0".to_string()
+        } else {
+            format!("utun-{}", sanitized)
+        }
+    }
+
+    /// Decode a base64 WireGuard key into the lowercase hex the UAPI expects.
+    fn base64_key_to_hex(key: &str) -> Result<String, WireGuardError> {
+        let bytes = base64_decode(key)
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Invalid base64 key: {}", e)))?;
+        Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Spawn the userspace process for `interface` if it isn't already running.
+    fn ensure_tunnel_process(&self, interface: &str) -> Result<(), WireGuardError> {
+        let socket_path = Self::uapi_socket_path(interface);
+        if std::path::Path::new(&socket_path).exists() {
+            return Ok(());
+        }
+
+        let binary = self.find_userspace_binary().ok_or(WireGuardError::NotInstalled)?;
+
+        Command::new(&binary)
+            .arg(interface)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to spawn {}: {}", binary, e)))?;
+
+        // Give the process a moment to create its UAPI socket.
+        for _ in 0..20 {
+            if std::path::Path::new(&socket_path).exists() {
+                return Ok(());
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        Err(WireGuardError::CommandFailed(format!(
+            "{} did not create a UAPI socket at {}",
+            binary, socket_path
+        )))
+    }
+
+    fn uapi_exchange(interface: &str, request: &str) -> Result<String, WireGuardError> {
+        let socket_path = Self::uapi_socket_path(interface);
+        let mut stream = UnixStream::connect(&socket_path)
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to connect to UAPI socket: {}", e)))?;
+
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to write to UAPI socket: {}", e)))?;
+
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to read from UAPI socket: {}", e)))?;
+
+        Ok(response)
+    }
+
+    fn build_set_request(&self, config: &WireGuardConfig) -> Result<String, WireGuardError> {
+        let mut req = String::from("set=1\n");
+        req.push_str(&format!("private_key={}\n", Self::base64_key_to_hex(&config.private_key)?));
+
+        if let Some(fwmark) = config.fwmark {
+            req.push_str(&format!("fwmark={}\n", fwmark));
+        }
+
+        if let Some(listen_port) = config.listen_port {
+            req.push_str(&format!("listen_port={}\n", listen_port));
+        }
+
+        for peer in &config.peers {
+            req.push_str(&format!("public_key={}\n", Self::base64_key_to_hex(&peer.public_key)?));
+            req.push_str("replace_allowed_ips=true\n");
+            for cidr in peer.allowed_ips.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                req.push_str(&format!("allowed_ip={}\n", cidr));
+            }
+            if let Some(endpoint) = &peer.endpoint {
+                req.push_str(&format!("endpoint={}\n", endpoint));
+            }
+            if let Some(keepalive) = peer.persistent_keepalive {
+                req.push_str(&format!("persistent_keepalive_interval={}\n", keepalive));
+            }
+        }
+        req.push('\n');
+
+        Ok(req)
+    }
+
+    /// Parse the key/value lines a `get=1` request returns into per-peer
+    /// stats, starting a new peer at each `public_key=` line the same way
+    /// the UAPI groups them.
+    fn parse_uapi_peers(response: &str) -> Vec<PeerStats> {
+        let mut peers = Vec::new();
+        let mut current: Option<PeerStats> = None;
+
+        for line in response.lines() {
+            let Some((key, value)) = line.split_once('=') else { continue };
+
+            if key == "public_key" {
+                if let Some(peer) = current.take() {
+                    peers.push(peer);
+                }
+                current = Some(PeerStats {
+                    public_key: hex_key_to_base64(value).unwrap_or_else(|_| value.to_string()),
+                    endpoint: None,
+                    allowed_ips: String::new(),
+                    last_handshake: None,
+                    rx_bytes: 0,
+                    tx_bytes: 0,
+                });
+                continue;
+            }
+
+            let Some(peer) = current.as_mut() else { continue };
+            match key {
+                "endpoint" => peer.endpoint = Some(value.to_string()),
+                "allowed_ip" => {
+                    if peer.allowed_ips.is_empty() {
+                        peer.allowed_ips = value.to_string();
+                    } else {
+                        peer.allowed_ips.push_str(", ");
+                        peer.allowed_ips.push_str(value);
+                    }
+                }
+                "last_handshake_time_sec" => {
+                    peer.last_handshake = value.parse::<u64>().ok().filter(|&secs| secs != 0)
+                        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+                }
+                "rx_bytes" => peer.rx_bytes = value.parse().unwrap_or(0),
+                "tx_bytes" => peer.tx_bytes = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+
+        if let Some(peer) = current.take() {
+            peers.push(peer);
+        }
+
+        peers
+    }
+}
+
+impl WireGuardPlatform for UserspacePlatform {
+    fn apply_config(&self, config: &WireGuardConfig) -> Result<String, WireGuardError> {
+        let interface = self.generate_interface_name(&config.name);
+
+        self.ensure_tunnel_process(&interface)?;
+
+        let request = self.build_set_request(config)?;
+        let response = Self::uapi_exchange(&interface, &request)?;
+
+        if !response.trim().eq("errno=0") {
+            return Err(WireGuardError::CommandFailed(format!(
+                "UAPI configuration failed: {}",
+                response.trim()
+            )));
+        }
+
+        Ok(interface)
+    }
+
+    fn disconnect(&self, interface: &str) -> Result<(), WireGuardError> {
+        let socket_path = Self::uapi_socket_path(interface);
+        if !std::path::Path::new(&socket_path).exists() {
+            return Err(WireGuardError::InterfaceNotFound(interface.to_string()));
+        }
+
+        // Userspace tunnels have no kernel state to tear down separately;
+        // killing the process via its socket going away is handled by the
+        // process owning the interface. We just remove the socket file.
+        std::fs::remove_file(&socket_path)
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to remove UAPI socket: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn get_status(&self, interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
+        let interface = match interface {
+            Some(iface) => iface.to_string(),
+            None => return Ok(ConnectionStatus {
+                connected: false,
+                current_config: None,
+                interface: None,
+                peers: vec![],
+            }),
+        };
+
+        let response = Self::uapi_exchange(&interface, "get=1\n\n")?;
+        let peers = Self::parse_uapi_peers(&response);
+        let connected = peers.iter().any(|p| p.last_handshake.is_some());
+
+        Ok(ConnectionStatus {
+            connected,
+            current_config: if connected { Some(interface.clone()) } else { None },
+            interface: Some(interface),
+            peers,
+        })
+    }
+
+    fn list_interfaces(&self) -> Result<Vec<String>, WireGuardError> {
+        let run_dir = std::path::Path::new("/var/run/wireguard");
+        if !run_dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let interfaces = std::fs::read_dir(run_dir)
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to read {}: {}", run_dir.display(), e)))?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.strip_suffix(".sock").map(|s| s.to_string())
+            })
+            .collect();
+
+        Ok(interfaces)
+    }
+
+    fn is_wireguard_installed(&self) -> bool {
+        self.find_userspace_binary().is_some()
+    }
+
+    fn wg_quick_path(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Decode a lowercase hex UAPI key back into the base64 representation used
+/// everywhere else in the config/stats model.
+fn hex_key_to_base64(hex: &str) -> Result<String, String> {
+    if hex.len() % 2 != 0 {
+        return Err("odd-length hex string".to_string());
+    }
+
+    let bytes: Result<Vec<u8>, _> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect();
+    let bytes = bytes.map_err(|e| e.to_string())?;
+
+    Ok(base64_encode(&bytes))
+}
+
+/// Minimal base64 encoder, the counterpart to `base64_decode` below.
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[(b2 & 0x3f) as usize] as char } else { '=' });
+    }
+
+    out
+}
+
+/// Minimal base64 decoder so the UAPI key conversion doesn't need a crate
+/// dependency beyond what the rest of the platform layer already pulls in.
+fn base64_decode(input: &str) -> Result<Vec<u8>, String> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let input = input.trim().trim_end_matches('=');
+    let mut bits: u32 = 0;
+    let mut bit_count = 0;
+    let mut out = Vec::new();
+
+    for c in input.bytes() {
+        let value = ALPHABET
+            .iter()
+            .position(|&b| b == c)
+            .ok_or_else(|| format!("invalid base64 character: {}", c as char))?;
+
+        bits = (bits << 6) | value as u32;
+        bit_count += 6;
+
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_hex_base64_roundtrip() {
+        let key = "xTk2K9vN8jH9kL2mP3qRGyTqNy5GZgQWQ6Y5vN8jH=";
+        let hex = UserspacePlatform::base64_key_to_hex(key).unwrap();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex_key_to_base64(&hex).unwrap(), key);
+    }
+
+    #[test]
+    fn test_parse_uapi_peers() {
+        let response = concat!(
+            "public_key=d1b4a0f1e2c3a4b5c6d7e8f9a0b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9\n",
+            "endpoint=203.0.113.1:51820\n",
+            "last_handshake_time_sec=1700000000\n",
+            "rx_bytes=1024\n",
+            "tx_bytes=2048\n",
+            "allowed_ip=10.0.0.2/32\n",
+            "public_key=a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2\n",
+            "last_handshake_time_sec=0\n",
+            "rx_bytes=0\n",
+            "tx_bytes=0\n",
+            "errno=0\n",
+        );
+
+        let peers = UserspacePlatform::parse_uapi_peers(response);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].endpoint.as_deref(), Some("203.0.113.1:51820"));
+        assert_eq!(peers[0].allowed_ips, "10.0.0.2/32");
+        assert!(peers[0].last_handshake.is_some());
+        assert_eq!(peers[0].rx_bytes, 1024);
+        assert!(peers[1].last_handshake.is_none());
+    }
+}
@@ -1,9 +1,15 @@
 /// Windows-specific WireGuard implementation
 ///
-/// This is a placeholder implementation for future Windows support.
-
+/// Drives the official WireGuard for Windows tooling (`wireguard.exe` /
+/// `wg.exe`), which manages tunnels as Windows services rather than through
+/// wg-quick. See https://www.wireguard.com/install/ for the tool layout.
 use super::{WireGuardError, WireGuardPlatform};
-use crate::wireguard::{WireGuardConfig, ConnectionStatus};
+use crate::wireguard::{config_to_wg_quick_format, ConnectionStatus};
+use crate::wireguard::WireGuardConfig;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use tempfile::Builder;
 
 pub struct WindowsPlatform;
 
@@ -11,31 +17,186 @@ impl WindowsPlatform {
     pub fn new() -> Self {
         Self
     }
+
+    /// Find `wireguard.exe` under `%ProgramFiles%\WireGuard` or on PATH.
+    fn find_wireguard_exe(&self) -> Option<String> {
+        if let Ok(program_files) = std::env::var("ProgramFiles") {
+            let candidate = PathBuf::from(program_files).join("WireGuard").join("wireguard.exe");
+            if candidate.exists() {
+                return Some(candidate.to_string_lossy().to_string());
+            }
+        }
+
+        if let Ok(output) = Command::new("where").arg("wireguard.exe").output() {
+            if output.status.success() {
+                if let Ok(path) = String::from_utf8(output.stdout) {
+                    if let Some(first) = path.lines().next() {
+                        return Some(first.trim().to_string());
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Find `wg.exe` under `%ProgramFiles%\WireGuard` or on PATH.
+    fn find_wg_exe(&self) -> Option<String> {
+        if let Ok(program_files) = std::env::var("ProgramFiles") {
+            let candidate = PathBuf::from(program_files).join("WireGuard").join("wg.exe");
+            if candidate.exists() {
+                return Some(candidate.to_string_lossy().to_string());
+            }
+        }
+
+        if let Ok(output) = Command::new("where").arg("wg.exe").output() {
+            if output.status.success() {
+                if let Ok(path) = String::from_utf8(output.stdout) {
+                    if let Some(first) = path.lines().next() {
+                        return Some(first.trim().to_string());
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Write the config to a temp `.conf` file; the tunnel service name is
+    /// derived from the file stem, matching `wireguard.exe`'s convention.
+    /// `rand_bytes(0)` is required here: `Builder` otherwise inserts random
+    /// characters *between* `prefix` and `suffix`, so the file (and thus the
+    /// service `wireguard.exe` actually installs) would be named
+    /// `"{tunnel_name}-{random}"` rather than the bare `tunnel_name` we
+    /// return to the caller.
+    fn write_tunnel_config(&self, config: &WireGuardConfig) -> Result<(PathBuf, String), WireGuardError> {
+        let wg_format = config_to_wg_quick_format(config);
+        let tunnel_name = config.name.replace(' ', "-");
+
+        let mut temp_file = Builder::new()
+            .prefix(&tunnel_name)
+            .suffix(".conf")
+            .rand_bytes(0)
+            .tempfile()
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to create temp file: {}", e)))?;
+
+        temp_file
+            .write_all(wg_format.as_bytes())
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to write config: {}", e)))?;
+
+        let path = temp_file.path().to_path_buf();
+        temp_file
+            .keep()
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to persist temp file: {}", e)))?;
+
+        Ok((path, tunnel_name))
+    }
 }
 
 impl WireGuardPlatform for WindowsPlatform {
-    fn apply_config(&self, _config: &WireGuardConfig) -> Result<String, WireGuardError> {
-        Err(WireGuardError::PlatformNotSupported)
+    fn apply_config(&self, config: &WireGuardConfig) -> Result<String, WireGuardError> {
+        let wireguard_exe = self.find_wireguard_exe().ok_or(WireGuardError::NotInstalled)?;
+
+        let (config_path, tunnel_name) = self.write_tunnel_config(config)?;
+
+        let output = Command::new(&wireguard_exe)
+            .args(["/installtunnelservice", config_path.to_str().unwrap()])
+            .output()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to invoke wireguard.exe: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(WireGuardError::CommandFailed(stderr.to_string()));
+        }
+
+        Ok(tunnel_name)
     }
 
-    fn disconnect(&self, _interface: &str) -> Result<(), WireGuardError> {
-        Err(WireGuardError::PlatformNotSupported)
+    fn disconnect(&self, interface: &str) -> Result<(), WireGuardError> {
+        let wireguard_exe = self.find_wireguard_exe().ok_or(WireGuardError::NotInstalled)?;
+
+        let output = Command::new(&wireguard_exe)
+            .args(["/uninstalltunnelservice", interface])
+            .output()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to invoke wireguard.exe: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(WireGuardError::CommandFailed(stderr.to_string()));
+        }
+
+        Ok(())
     }
 
-    fn get_status(&self, _interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
-        Err(WireGuardError::PlatformNotSupported)
+    fn get_status(&self, interface: Option<&str>) -> Result<ConnectionStatus, WireGuardError> {
+        let wg_exe = self.find_wg_exe().ok_or(WireGuardError::NotInstalled)?;
+
+        let interfaces = if let Some(iface) = interface {
+            vec![iface.to_string()]
+        } else {
+            self.list_interfaces()?
+        };
+
+        if interfaces.is_empty() {
+            return Ok(ConnectionStatus {
+                connected: false,
+                current_config: None,
+                interface: None,
+                peers: vec![],
+            });
+        }
+
+        let output = Command::new(&wg_exe)
+            .args(["show", &interfaces[0], "dump"])
+            .output()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to check status: {}", e)))?;
+
+        let dump = String::from_utf8_lossy(&output.stdout);
+        let peers = super::parse_wg_dump(&dump);
+        let is_connected = output.status.success() && peers.iter().any(|p| p.last_handshake.is_some());
+
+        Ok(ConnectionStatus {
+            connected: is_connected,
+            current_config: if is_connected { Some(interfaces[0].clone()) } else { None },
+            interface: if is_connected { Some(interfaces[0].clone()) } else { None },
+            peers,
+        })
     }
 
     fn list_interfaces(&self) -> Result<Vec<String>, WireGuardError> {
-        Ok(vec![])
+        let wg_exe = self.find_wg_exe().ok_or(WireGuardError::NotInstalled)?;
+
+        let output = Command::new(&wg_exe)
+            .arg("show")
+            .output()
+            .map_err(|e| WireGuardError::CommandFailed(format!("Failed to list interfaces: {}", e)))?;
+
+        if !output.status.success() {
+            return Ok(vec![]);
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let interfaces: Vec<String> = stdout
+            .lines()
+            .filter_map(|line| {
+                let name = line.trim();
+                if !name.is_empty() {
+                    Some(name.to_string())
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(interfaces)
     }
 
     fn is_wireguard_installed(&self) -> bool {
-        false
+        self.find_wireguard_exe().is_some() && self.find_wg_exe().is_some()
     }
 
     fn wg_quick_path(&self) -> Option<String> {
-        None
+        // Windows has no wg-quick; wireguard.exe plays that role instead.
+        self.find_wireguard_exe()
     }
 }
-
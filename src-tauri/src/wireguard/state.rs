@@ -0,0 +1,174 @@
+/// Persisted tunnel state.
+///
+/// Tracks, for each interface we've brought up, which config file produced
+/// it and which named profile it came from, so `disconnect`/`get_status`
+/// don't have to guess at a config path the way the early wg-quick probing
+/// code did. Also holds named, persistent tunnel profiles (with an
+/// `autostart` flag) independently of the in-memory `WireGuardConfig` the
+/// UI works with, so secrets referenced via `private_key_file` aren't
+/// embedded in the profile itself.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::platform::WireGuardError;
+
+/// A named, persistent tunnel profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TunnelProfile {
+    pub name: String,
+    pub config_path: PathBuf,
+    /// Path to a file holding the private key, kept separate from the
+    /// profile so the key isn't serialized alongside it.
+    pub private_key_file: Option<PathBuf>,
+    pub autostart: bool,
+}
+
+impl TunnelProfile {
+    /// Resolve the profile's private key via `private_key_file`, generating
+    /// and persisting one there if it doesn't exist yet. `None` if the
+    /// profile has no file configured - its `WireGuardConfig` is assumed to
+    /// carry the key inline instead.
+    pub fn resolve_private_key(&self) -> Result<Option<String>, WireGuardError> {
+        match &self.private_key_file {
+            Some(path) => Ok(Some(super::keys::PrivateKeySource::File(path.clone()).resolve()?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Records exactly which config file and interface a profile is currently
+/// applied to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveTunnel {
+    pub profile_name: String,
+    pub interface: String,
+    pub config_path: PathBuf,
+}
+
+/// The full on-disk tunnel state, stored as a single JSON file in the app's
+/// data directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TunnelState {
+    #[serde(default)]
+    pub profiles: Vec<TunnelProfile>,
+    /// Active tunnels keyed by interface name.
+    #[serde(default)]
+    pub active: HashMap<String, ActiveTunnel>,
+}
+
+impl TunnelState {
+    fn state_file_path() -> Result<PathBuf, WireGuardError> {
+        let dir = dirs::data_dir()
+            .ok_or_else(|| WireGuardError::ConfigInvalid("Could not determine app data directory".to_string()))?
+            .join("vipn");
+
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to create state directory: {}", e)))?;
+
+        Ok(dir.join("tunnel_state.json"))
+    }
+
+    /// Load the persisted state, or an empty one if none exists yet.
+    pub fn load() -> Result<Self, WireGuardError> {
+        let path = Self::state_file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to read state file: {}", e)))?;
+
+        serde_json::from_str(&contents)
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to parse state file: {}", e)))
+    }
+
+    pub fn save(&self) -> Result<(), WireGuardError> {
+        let path = Self::state_file_path()?;
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to serialize state: {}", e)))?;
+
+        std::fs::write(&path, contents)
+            .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to write state file: {}", e)))
+    }
+
+    /// Record that `interface` is now running from `config_path`, attributed
+    /// to `profile_name`, and persist it immediately.
+    pub fn record_active(&mut self, profile_name: &str, interface: &str, config_path: PathBuf) -> Result<(), WireGuardError> {
+        self.active.insert(
+            interface.to_string(),
+            ActiveTunnel {
+                profile_name: profile_name.to_string(),
+                interface: interface.to_string(),
+                config_path,
+            },
+        );
+        self.save()
+    }
+
+    /// Remove and return the active-tunnel record for `interface`, if any.
+    pub fn clear_active(&mut self, interface: &str) -> Result<Option<ActiveTunnel>, WireGuardError> {
+        let removed = self.active.remove(interface);
+        self.save()?;
+        Ok(removed)
+    }
+
+    pub fn upsert_profile(&mut self, profile: TunnelProfile) -> Result<(), WireGuardError> {
+        self.profiles.retain(|p| p.name != profile.name);
+        self.profiles.push(profile);
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_tunnel_roundtrip() {
+        let mut state = TunnelState::default();
+        state.active.insert(
+            "wg0".to_string(),
+            ActiveTunnel {
+                profile_name: "work".to_string(),
+                interface: "wg0".to_string(),
+                config_path: PathBuf::from("/tmp/wg0.conf"),
+            },
+        );
+
+        let json = serde_json::to_string(&state).unwrap();
+        let deserialized: TunnelState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized.active["wg0"].profile_name, "work");
+    }
+
+    #[test]
+    fn test_resolve_private_key_none_without_file() {
+        let profile = TunnelProfile {
+            name: "work".to_string(),
+            config_path: PathBuf::from("/tmp/wg0.conf"),
+            private_key_file: None,
+            autostart: false,
+        };
+
+        assert!(profile.resolve_private_key().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_resolve_private_key_generates_from_file() {
+        let path = std::env::temp_dir().join("vipn-test-profile-private-key.key");
+        let _ = std::fs::remove_file(&path);
+
+        let profile = TunnelProfile {
+            name: "work".to_string(),
+            config_path: PathBuf::from("/tmp/wg0.conf"),
+            private_key_file: Some(path.clone()),
+            autostart: false,
+        };
+
+        let key = profile.resolve_private_key().unwrap().expect("should generate a key");
+        assert_eq!(profile.resolve_private_key().unwrap(), Some(key));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
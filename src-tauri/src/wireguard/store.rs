@@ -0,0 +1,162 @@
+/// On-disk store for named `WireGuardConfig`s.
+///
+/// Configs are persisted as standard wg-quick `.conf` files under the app's
+/// data directory (one file per name) rather than a single JSON blob, so
+/// they're the same format a user could hand-edit or import elsewhere. This
+/// replaces the old `get_config`/`get_config_by_id`/`fetch_config_list_from_server`
+/// mocks with a real store.
+use super::platform::WireGuardError;
+use super::{config_to_wg_quick_format, WireGuardConfig};
+use std::path::PathBuf;
+
+fn configs_dir() -> Result<PathBuf, WireGuardError> {
+    let dir = dirs::data_dir()
+        .ok_or_else(|| WireGuardError::ConfigInvalid("Could not determine app data directory".to_string()))?
+        .join("vipn")
+        .join("configs");
+
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to create configs directory: {}", e)))?;
+
+    Ok(dir)
+}
+
+/// The path a named config is (or would be) stored at. `name` reaches here
+/// straight from the Tauri-exposed `save_config`/`delete_config`/`list_configs`
+/// commands, which take arbitrary strings from the webview, so it's rejected
+/// unless it's a single plain filename component - no path separators, no
+/// `.`/`..`, not empty - to keep it from escaping `configs_dir()`.
+pub fn config_path(name: &str) -> Result<PathBuf, WireGuardError> {
+    let is_plain_component = matches!(
+        std::path::Path::new(name).components().collect::<Vec<_>>().as_slice(),
+        [std::path::Component::Normal(_)]
+    );
+    if !is_plain_component {
+        return Err(WireGuardError::ConfigInvalid(format!("Invalid config name: {}", name)));
+    }
+
+    Ok(configs_dir()?.join(format!("{}.conf", name)))
+}
+
+/// Save (or overwrite) a named config as a wg-quick `.conf` file.
+pub fn save_config(config: &WireGuardConfig) -> Result<(), WireGuardError> {
+    let path = config_path(&config.name)?;
+    let contents = config_to_wg_quick_format(config);
+
+    std::fs::write(&path, contents)
+        .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+/// Delete a named config. Returns `true` if a config was removed.
+pub fn delete_config(name: &str) -> Result<bool, WireGuardError> {
+    let path = config_path(name)?;
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    std::fs::remove_file(&path)
+        .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to delete {}: {}", path.display(), e)))?;
+
+    Ok(true)
+}
+
+/// Load a named config, if it exists.
+pub fn get_config(name: &str) -> Result<Option<WireGuardConfig>, WireGuardError> {
+    let path = config_path(name)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    WireGuardConfig::from_file(&path).map(Some)
+}
+
+/// List every persisted config, named after the file it was loaded from.
+pub fn list_configs() -> Result<Vec<WireGuardConfig>, WireGuardError> {
+    let dir = configs_dir()?;
+
+    let mut configs = Vec::new();
+    for entry in std::fs::read_dir(&dir)
+        .map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to read {}: {}", dir.display(), e)))?
+    {
+        let entry = entry.map_err(|e| WireGuardError::ConfigInvalid(format!("Failed to read config entry: {}", e)))?;
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("conf") {
+            configs.push(WireGuardConfig::from_file(&path)?);
+        }
+    }
+
+    configs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(configs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wireguard::Peer;
+
+    fn test_config(name: &str) -> WireGuardConfig {
+        WireGuardConfig {
+            name: name.to_string(),
+            private_key: "test-private-key".to_string(),
+            addresses: vec!["10.0.0.2/24".to_string()],
+            dns: vec![],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![Peer {
+                name: "peer-1".to_string(),
+                public_key: "test-public-key".to_string(),
+                preshared_key: None,
+                endpoint: Some("vpn.example.com:51820".to_string()),
+                allowed_ips: "0.0.0.0/0".to_string(),
+                persistent_keepalive: Some(25),
+            }],
+        }
+    }
+
+    /// Configs persist under the shared app data directory, so exercise the
+    /// full save/get/delete cycle against one unique name instead of the
+    /// usual isolated-tempdir setup, to avoid clobbering a concurrently
+    /// running test's config.
+    #[test]
+    fn test_save_get_delete_config_roundtrip() {
+        let config = test_config("vipn-store-test-roundtrip");
+
+        save_config(&config).unwrap();
+        let loaded = get_config(&config.name).unwrap().expect("config should exist after save");
+        assert_eq!(loaded.private_key, config.private_key);
+        assert_eq!(loaded.peers[0].public_key, config.peers[0].public_key);
+
+        assert!(delete_config(&config.name).unwrap());
+        assert!(get_config(&config.name).unwrap().is_none());
+        assert!(!delete_config(&config.name).unwrap());
+    }
+
+    #[test]
+    fn test_list_configs_includes_saved_config() {
+        let config = test_config("vipn-store-test-list");
+        save_config(&config).unwrap();
+
+        let configs = list_configs().unwrap();
+        assert!(configs.iter().any(|c| c.name == config.name));
+
+        delete_config(&config.name).unwrap();
+    }
+
+    #[test]
+    fn test_config_path_rejects_path_traversal() {
+        assert!(config_path("../../../etc/cron.d/x").is_err());
+        assert!(config_path("../escaped").is_err());
+        assert!(config_path("subdir/name").is_err());
+        assert!(config_path("/etc/passwd").is_err());
+        assert!(config_path("..").is_err());
+        assert!(config_path(".").is_err());
+        assert!(config_path("").is_err());
+    }
+
+    #[test]
+    fn test_config_path_accepts_plain_name() {
+        let path = config_path("vipn-store-test-plain-name").unwrap();
+        assert_eq!(path.file_name().unwrap().to_str().unwrap(), "vipn-store-test-plain-name.conf");
+    }
+}
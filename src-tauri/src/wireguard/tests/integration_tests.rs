@@ -7,6 +7,7 @@
 mod tests {
     use crate::wireguard::{
         WireGuardConfig,
+        Peer,
         apply_config,
         disconnect,
         get_connection_status,
@@ -31,12 +32,19 @@ mod tests {
         WireGuardConfig {
             name: "integration-test".to_string(),
             private_key: "cF7B1i7pHVXo0jRGyTqNy5GZgQWQ6Y5vN8jH9kL2mP3q=".to_string(),
-            public_key: "xTk2K9vN8jH9kL2mP3qRGyTqNy5GZgQWQ6Y5vN8jH=".to_string(),
-            endpoint: "127.0.0.1:51820".to_string(),
-            allowed_ips: "10.0.0.0/8".to_string(),
-            dns: Some("1.1.1.1".to_string()),
-            address: "10.0.0.2/24".to_string(),
-            persistent_keepalive: Some(25),
+            dns: vec!["1.1.1.1".to_string()],
+            addresses: vec!["10.0.0.2/24".to_string()],
+            fwmark: None,
+            mtu: None,
+            listen_port: None,
+            peers: vec![Peer {
+                name: "integration-test-peer".to_string(),
+                public_key: "xTk2K9vN8jH9kL2mP3qRGyTqNy5GZgQWQ6Y5vN8jH=".to_string(),
+                preshared_key: None,
+                endpoint: Some("127.0.0.1:51820".to_string()),
+                allowed_ips: "10.0.0.0/8".to_string(),
+                persistent_keepalive: Some(25),
+            }],
         }
     }
 
@@ -99,8 +107,8 @@ mod tests {
 
         // Verify values
         assert!(wg_format.contains(&config.private_key));
-        assert!(wg_format.contains(&config.public_key));
-        assert!(wg_format.contains(&config.endpoint));
+        assert!(wg_format.contains(&config.peers[0].public_key));
+        assert!(wg_format.contains(config.peers[0].endpoint.as_ref().unwrap()));
     }
 
     /// Test: Error handling for missing WireGuard